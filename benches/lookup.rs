@@ -0,0 +1,52 @@
+//! Lookup throughput, for comparing changes to the generated tables.
+//!
+//! Run with `cargo bench --bench lookup`, and again with `--features device-map` to
+//! compare the device lookup strategies.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use usb_ids::{Device, Devices, FromId, Vendor, VENDOR_IDS};
+
+/// Every 64th device in the DB, so the pairs are spread across vendors of all sizes.
+fn device_pairs() -> Vec<(u16, u16)> {
+    Devices::iter()
+        .step_by(64)
+        .map(|(_, device)| device.as_vid_pid())
+        .collect()
+}
+
+fn vendor_from_id(c: &mut Criterion) {
+    let ids: Vec<u16> = VENDOR_IDS.iter().copied().step_by(16).collect();
+
+    c.bench_function("Vendor::from_id", |b| {
+        b.iter(|| {
+            for &id in &ids {
+                black_box(Vendor::from_id(black_box(id)));
+            }
+        })
+    });
+}
+
+fn device_from_vid_pid(c: &mut Criterion) {
+    let pairs = device_pairs();
+
+    c.bench_function("Device::from_vid_pid", |b| {
+        b.iter(|| {
+            for &(vid, pid) in &pairs {
+                black_box(Device::from_vid_pid(black_box(vid), black_box(pid)));
+            }
+        })
+    });
+
+    // product IDs that are (almost always) absent still search the vendor's devices
+    let misses: Vec<(u16, u16)> = pairs.iter().map(|&(vid, _)| (vid, 0xfffe)).collect();
+    c.bench_function("Device::from_vid_pid (miss)", |b| {
+        b.iter(|| {
+            for &(vid, pid) in &misses {
+                black_box(Device::from_vid_pid(black_box(vid), black_box(pid)));
+            }
+        })
+    });
+}
+
+criterion_group!(benches, vendor_from_id, device_from_vid_pid);
+criterion_main!(benches);
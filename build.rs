@@ -1,7 +1,8 @@
+use std::collections::{BTreeMap, BTreeSet};
 use std::env;
 use std::fs;
 use std::io::{BufRead, BufReader, BufWriter, Write};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use phf_codegen::Map;
 use quote::quote;
@@ -12,7 +13,24 @@ use quote::quote;
  */
 
 // these are the definitions for the generated maps that will be written to the source file
-const VENDOR_PROLOGUE: &str = "static USB_IDS: phf::Map<u16, Vendor> = ";
+const VENDOR_PROLOGUE: &str = "static USB_IDS: phf::Map<u16, u16> = ";
+const VENDORS_PROLOGUE: &str = "const USB_VENDORS: &[Vendor] = ";
+const VENDOR_NAME_PROLOGUE: &str = "static USB_VENDOR_NAMES: phf::Map<&'static str, u16> = ";
+const VENDORS_BY_NAME_PROLOGUE: &str = "static USB_VENDORS_BY_NAME: &[u16] = ";
+const VENDOR_IDS_PROLOGUE: &str =
+    "/// The IDs of all vendors in the USB database, in ascending order.\npub const VENDOR_IDS: &[u16] = ";
+const CLASS_IDS_PROLOGUE: &str =
+    "/// The IDs of all classes in the USB database, in ascending order.\npub const CLASS_IDS: &[u8] = ";
+const DB_VERSION_PROLOGUE: &str =
+    "/// The version of the bundled USB database, from its header.\npub const DB_VERSION: &str = ";
+const DB_DATE_PROLOGUE: &str =
+    "/// The date of the bundled USB database, from its header.\npub const DB_DATE: &str = ";
+const VENDOR_COUNT_PROLOGUE: &str =
+    "/// The number of vendors in the USB database.\npub const VENDOR_COUNT: usize = ";
+const DEVICE_COUNT_PROLOGUE: &str =
+    "/// The total number of devices, across all vendors, in the USB database.\npub const DEVICE_COUNT: usize = ";
+const VENDOR_WEBSITE_PROLOGUE: &str = "static USB_VENDOR_WEBSITES: phf::Map<u16, &'static str> = ";
+const DEVICE_IDS_PROLOGUE: &str = "static USB_DEVICE_IDS: phf::Map<u32, (u16, u16)> = ";
 const CLASS_PROLOGUE: &str = "static USB_CLASSES: phf::Map<u8, Class> = ";
 const AUDIO_TERMINAL_PROLOGUE: &str = "static USB_AUDIO_TERMINALS: phf::Map<u16, AudioTerminal> = ";
 const HID_ID_PROLOGUE: &str = "static USB_HID_IDS: phf::Map<u8, Hid> = ";
@@ -34,12 +52,61 @@ struct CgVendor {
     devices: Vec<CgDevice>,
 }
 
+/// The vendors and their secondary indices, emitted together once the section ends.
+#[derive(Default)]
+struct CgVendorIndex {
+    /// Vendor ID to the vendor's generated tokens, kept in ID order for the sorted array.
+    vendors: BTreeMap<u16, String>,
+    /// Vendor ID to the vendor's device IDs, in the same (ascending) order as the generated slice.
+    device_ids: BTreeMap<u16, Vec<u16>>,
+    /// Lowercased vendor name to the lowest vendor ID with that name.
+    names: BTreeMap<String, u16>,
+    /// Every vendor's lowercased name and ID, for the name-sorted order.
+    sorted_names: Vec<(String, u16)>,
+    device_count: usize,
+}
+
+impl CgVendorIndex {
+    fn insert(&mut self, id: u16, name: &str) {
+        self.names
+            .entry(name.to_lowercase())
+            .and_modify(|v| *v = (*v).min(id))
+            .or_insert(id);
+        self.sorted_names.push((name.to_lowercase(), id));
+    }
+
+    fn contains(&self, id: u16) -> bool {
+        self.vendors.contains_key(&id)
+    }
+
+    /// Adds a finished vendor. A duplicate ID has already been reported as malformed, so
+    /// the first vendor with the ID is kept.
+    fn push(&mut self, vendor: &CgVendor) {
+        if self.contains(vendor.id) {
+            return;
+        }
+
+        self.vendors.insert(vendor.id, quote!(#vendor).to_string());
+        let devices = sorted_by_id(&vendor.devices)
+            .into_iter()
+            .map(|d| d.id)
+            .collect();
+        self.device_ids.insert(vendor.id, devices);
+    }
+}
+
 struct CgDevice {
     id: u16,
     name: String,
     interfaces: Vec<CgInterface>,
 }
 
+impl CgEntry<u16> for CgDevice {
+    fn id(&self) -> u16 {
+        self.id
+    }
+}
+
 struct CgClass {
     id: u8,
     name: String,
@@ -71,6 +138,13 @@ impl<T: Copy> CgEntry<T> for CgType<T> {
     }
 }
 
+/// Returns the entries sorted by ID, so that the generated slices can be binary searched.
+fn sorted_by_id<T: Ord, E: CgEntry<T>>(entries: &[E]) -> Vec<&E> {
+    let mut sorted: Vec<&E> = entries.iter().collect();
+    sorted.sort_by_key(|e| e.id());
+    sorted
+}
+
 type CgInterface = CgType<u8>;
 type CgProtocol = CgType<u8>;
 type CgAtType = CgType<u16>;
@@ -88,8 +162,8 @@ type CgTerminalType = CgType<u16>;
 /// Parser state parses only the type for the current section, this is because some
 /// parsers are ambiguous without context; device.interface == subclass.protocol for example.
 enum ParserState {
-    Vendors(Map<u16>, Option<CgVendor>, u16),
-    Classes(Map<u8>, Option<CgClass>, u8),
+    Vendors(Map<u16>, Option<CgVendor>, CgVendorIndex),
+    Classes(Map<u8>, Option<CgClass>, Vec<u8>),
     AtType(Map<u16>, Option<CgAtType>),
     HidType(Map<u8>, Option<CgHidType>),
     RType(Map<u8>, Option<CgRType>),
@@ -122,11 +196,11 @@ impl ParserState {
     /// Emit any pending entries to the map
     fn emit(&mut self) {
         match self {
-            ParserState::Vendors(m, Some(vendor), _) => {
-                m.entry(vendor.id, &quote!(#vendor).to_string());
+            ParserState::Vendors(_, Some(vendor), index) => {
+                index.push(vendor);
             }
-            ParserState::Classes(m, Some(class), _) => {
-                m.entry(class.id, &quote!(#class).to_string());
+            ParserState::Classes(m, Some(class), ids) => {
+                push_class(m, ids, class);
             }
             ParserState::AtType(m, Some(t)) | ParserState::TerminalType(m, Some(t)) => {
                 m.entry(t.id(), &quote!(#t).to_string());
@@ -148,72 +222,97 @@ impl ParserState {
         }
     }
 
+    /// Return the position of the current state in the standard ordering of the file
+    fn rank(&self) -> usize {
+        match self {
+            ParserState::Vendors(_, _, _) => 0,
+            ParserState::Classes(_, _, _) => 1,
+            ParserState::AtType(_, _) => 2,
+            ParserState::HidType(_, _) => 3,
+            ParserState::RType(_, _) => 4,
+            ParserState::BiasType(_, _) => 5,
+            ParserState::PhyType(_, _) => 6,
+            ParserState::HutType(_, _) => 7,
+            ParserState::Lang(_, _) => 8,
+            ParserState::CountryCode(_, _) => 9,
+            ParserState::TerminalType(_, _) => 10,
+        }
+    }
+
     /// Detects the next state based on the header line
     ///
     /// Not very efficient but since it only checks # lines and required length it is not terrible
-    fn next_from_header(&mut self, line: &str, output: &mut impl Write) -> Option<ParserState> {
+    ///
+    /// The parser relies on the standard ordering of the sections, so a section header that
+    /// is out of order (or a section before any vendor) is an error rather than silently
+    /// producing incomplete maps.
+    fn next_from_header(
+        &mut self,
+        line: &str,
+        output: &mut impl Write,
+    ) -> Result<Option<ParserState>, String> {
         if line.len() < 7 || !line.starts_with('#') {
-            return None;
+            return Ok(None);
         }
 
-        match &line[..7] {
-            "# C cla" => {
-                self.finalize(output);
-                Some(ParserState::Classes(Map::<u8>::new(), None, 0u8))
-            }
-            "# AT te" => {
-                self.finalize(output);
-                Some(ParserState::AtType(Map::<u16>::new(), None))
-            }
-            "# HID d" => {
-                self.finalize(output);
-                Some(ParserState::HidType(Map::<u8>::new(), None))
-            }
-            "# R ite" => {
-                self.finalize(output);
-                Some(ParserState::RType(Map::<u8>::new(), None))
-            }
-            "# BIAS " => {
-                self.finalize(output);
-                Some(ParserState::BiasType(Map::<u8>::new(), None))
-            }
-            "# PHY i" => {
-                self.finalize(output);
-                Some(ParserState::PhyType(Map::<u8>::new(), None))
-            }
-            "# HUT h" => {
-                self.finalize(output);
-                Some(ParserState::HutType(Map::<u8>::new(), None))
-            }
-            "# L lan" => {
-                self.finalize(output);
-                Some(ParserState::Lang(Map::<u16>::new(), None))
-            }
-            "# HCC c" => {
-                self.finalize(output);
-                Some(ParserState::CountryCode(Map::<u8>::new(), None))
-            }
-            "# VT te" => {
-                self.finalize(output);
-                Some(ParserState::TerminalType(Map::<u16>::new(), None))
+        let next = match &line[..7] {
+            "# C cla" => ParserState::Classes(Map::<u8>::new(), None, vec![]),
+            "# AT te" => ParserState::AtType(Map::<u16>::new(), None),
+            "# HID d" => ParserState::HidType(Map::<u8>::new(), None),
+            "# R ite" => ParserState::RType(Map::<u8>::new(), None),
+            "# BIAS " => ParserState::BiasType(Map::<u8>::new(), None),
+            "# PHY i" => ParserState::PhyType(Map::<u8>::new(), None),
+            "# HUT h" => ParserState::HutType(Map::<u8>::new(), None),
+            "# L lan" => ParserState::Lang(Map::<u16>::new(), None),
+            "# HCC c" => ParserState::CountryCode(Map::<u8>::new(), None),
+            "# VT te" => ParserState::TerminalType(Map::<u16>::new(), None),
+            _ => return Ok(None),
+        };
+
+        if let ParserState::Vendors(_, None, index) = self {
+            if index.vendors.is_empty() {
+                return Err(format!(
+                    "section header {:?} appears before any vendor; vendors must come first",
+                    line
+                ));
             }
-            _ => None,
         }
+        if next.rank() <= self.rank() {
+            return Err(format!(
+                "section header {:?} is out of order; sections must follow the standard usb.ids ordering",
+                line
+            ));
+        }
+
+        self.finalize(output);
+        Ok(Some(next))
     }
 
     /// Process a line of input for the current state
-    fn process(&mut self, line: &str) {
+    ///
+    /// Returns a description of the problem if the line is malformed for the current state.
+    fn process(&mut self, line: &str) -> Result<(), &'static str> {
         if line.is_empty() || line.starts_with('#') {
-            return;
+            return Ok(());
         }
 
         // Switch parser state based on line prefix and current state
         // this relies on ordering of classes and types in the file...
         match self {
-            ParserState::Vendors(m, ref mut curr_vendor, ref mut curr_device_id) => {
+            ParserState::Vendors(_, ref mut curr_vendor, index) => {
+                // Top-level entries of later sections (e.g. `C 00  ...`) would otherwise be dropped
+                if !line.starts_with('\t') && parser::vendor(line).is_err() {
+                    return Err("entry outside of its section whilst parsing vendors");
+                }
+
                 if let Ok((name, id)) = parser::vendor(line) {
                     if let Some(cv) = curr_vendor {
-                        m.entry(cv.id, &quote!(#cv).to_string());
+                        index.push(cv);
+                    }
+
+                    let duplicate = index.contains(id);
+                    if !duplicate {
+                        index.insert(id, name);
                     }
 
                     // Set our new vendor as the current vendor.
@@ -222,36 +321,49 @@ impl ParserState {
                         name: name.into(),
                         devices: vec![],
                     });
+
+                    if duplicate {
+                        return Err("duplicate vendor ID");
+                    }
                 // We should always have a current vendor; failure here indicates a malformed input.
                 } else {
                     let curr_vendor = curr_vendor
                         .as_mut()
-                        .expect("No parent vendor whilst parsing vendors");
+                        .ok_or("no parent vendor whilst parsing vendors")?;
                     if let Ok((name, id)) = parser::device(line) {
+                        if curr_vendor.devices.iter().any(|d| d.id == id) {
+                            return Err("duplicate device ID within its vendor");
+                        }
+
                         curr_vendor.devices.push(CgDevice {
                             id,
                             name: name.into(),
                             interfaces: vec![],
                         });
-                        *curr_device_id = id;
+                        index.device_count += 1;
                     } else if let Ok((name, id)) = parser::interface(line) {
+                        // Interfaces belong to the device line directly above them.
                         let curr_device = curr_vendor
                             .devices
-                            .iter_mut()
-                            .find(|d| d.id == *curr_device_id)
-                            .expect("No parent device whilst parsing interfaces");
-
-                        curr_device.interfaces.push(CgInterface {
-                            id,
-                            name: name.into(),
-                        });
+                            .last_mut()
+                            .ok_or("no parent device whilst parsing interfaces")?;
+
+                        // Still parsed without the feature, so malformed input is reported either way
+                        if cfg_feature("interfaces") {
+                            curr_device.interfaces.push(CgInterface {
+                                id,
+                                name: name.into(),
+                            });
+                        }
+                    } else {
+                        return Err("invalid device or interface line");
                     }
                 }
             }
-            ParserState::Classes(m, ref mut curr_class, ref mut curr_class_id) => {
+            ParserState::Classes(m, ref mut curr_class, ids) => {
                 if let Ok((name, id)) = parser::class(line) {
                     if let Some(cv) = curr_class {
-                        m.entry(cv.id, &quote!(#cv).to_string());
+                        push_class(m, ids, cv);
                     }
 
                     // Set our new class as the current class.
@@ -263,31 +375,32 @@ impl ParserState {
                 } else {
                     let curr_class = curr_class
                         .as_mut()
-                        .expect("No parent class whilst parsing classes");
+                        .ok_or("no parent class whilst parsing classes")?;
                     if let Ok((name, id)) = parser::sub_class(line) {
                         curr_class.sub_classes.push(CgSubClass {
                             id,
                             name: name.into(),
                             children: vec![],
                         });
-                        *curr_class_id = id;
                     } else if let Ok((name, id)) = parser::protocol(line) {
-                        let curr_device = curr_class
+                        // Protocols belong to the subclass line directly above them.
+                        let curr_sub_class = curr_class
                             .sub_classes
-                            .iter_mut()
-                            .find(|d| d.id == *curr_class_id)
-                            .expect("No parent sub-class whilst parsing protocols");
+                            .last_mut()
+                            .ok_or("no parent sub-class whilst parsing protocols")?;
 
-                        curr_device.children.push(CgProtocol {
+                        curr_sub_class.children.push(CgProtocol {
                             id,
                             name: name.into(),
                         });
+                    } else {
+                        return Err("invalid class, sub-class or protocol line");
                     }
                 }
             }
             ParserState::AtType(m, ref mut current) => {
                 let (name, id) =
-                    parser::audio_terminal_type(line).expect("Invalid audio terminal line");
+                    parser::audio_terminal_type(line).map_err(|_| "invalid audio terminal line")?;
                 if let Some(cv) = current {
                     m.entry(cv.id, &quote!(#cv).to_string());
                 }
@@ -299,7 +412,7 @@ impl ParserState {
                 });
             }
             ParserState::HidType(m, ref mut current) => {
-                let (name, id) = parser::hid_type(line).expect("Invalid hid type line");
+                let (name, id) = parser::hid_type(line).map_err(|_| "invalid hid type line")?;
                 if let Some(cv) = current {
                     m.entry(cv.id, &quote!(#cv).to_string());
                 }
@@ -311,7 +424,8 @@ impl ParserState {
                 });
             }
             ParserState::RType(m, ref mut current) => {
-                let (name, id) = parser::hid_item_type(line).expect("Invalid hid item type line");
+                let (name, id) =
+                    parser::hid_item_type(line).map_err(|_| "invalid hid item type line")?;
                 if let Some(cv) = current {
                     m.entry(cv.id, &quote!(#cv).to_string());
                 }
@@ -323,7 +437,7 @@ impl ParserState {
                 });
             }
             ParserState::BiasType(m, ref mut current) => {
-                let (name, id) = parser::bias_type(line).expect("Invalid bias type line");
+                let (name, id) = parser::bias_type(line).map_err(|_| "invalid bias type line")?;
                 if let Some(cv) = current {
                     m.entry(cv.id, &quote!(#cv).to_string());
                 }
@@ -335,7 +449,7 @@ impl ParserState {
                 });
             }
             ParserState::PhyType(m, ref mut current) => {
-                let (name, id) = parser::phy_type(line).expect("Invalid phy type line");
+                let (name, id) = parser::phy_type(line).map_err(|_| "invalid phy type line")?;
                 if let Some(cv) = current {
                     m.entry(cv.id, &quote!(#cv).to_string());
                 }
@@ -359,12 +473,16 @@ impl ParserState {
                         children: vec![],
                     });
                 } else {
-                    let curr_hut = current.as_mut().expect("No parent hut whilst parsing huts");
+                    let curr_hut = current
+                        .as_mut()
+                        .ok_or("no parent hut whilst parsing huts")?;
                     if let Ok((name, id)) = parser::hid_usage_name(line) {
                         curr_hut.children.push(CgHidUsage {
                             id,
                             name: name.into(),
                         });
+                    } else {
+                        return Err("invalid hut or hid usage line");
                     }
                 }
             }
@@ -383,17 +501,20 @@ impl ParserState {
                 } else {
                     let curr_lang = current
                         .as_mut()
-                        .expect("No parent lang whilst parsing langs");
+                        .ok_or("no parent lang whilst parsing langs")?;
                     if let Ok((name, id)) = parser::dialect(line) {
                         curr_lang.children.push(CgDialect {
                             id,
                             name: name.into(),
                         });
+                    } else {
+                        return Err("invalid lang or dialect line");
                     }
                 }
             }
             ParserState::CountryCode(m, ref mut current) => {
-                let (name, id) = parser::country_code(line).expect("Invalid country code line");
+                let (name, id) =
+                    parser::country_code(line).map_err(|_| "invalid country code line")?;
                 if let Some(cv) = current {
                     m.entry(cv.id, &quote!(#cv).to_string());
                 }
@@ -405,7 +526,8 @@ impl ParserState {
                 });
             }
             ParserState::TerminalType(m, ref mut current) => {
-                let (name, id) = parser::terminal_type(line).expect("Invalid terminal type line");
+                let (name, id) =
+                    parser::terminal_type(line).map_err(|_| "invalid terminal type line")?;
                 if let Some(cv) = current {
                     m.entry(cv.id, &quote!(#cv).to_string());
                 }
@@ -417,6 +539,8 @@ impl ParserState {
                 });
             }
         }
+
+        Ok(())
     }
 
     /// Emit the prologue and map to the output file.
@@ -431,11 +555,64 @@ impl ParserState {
 
         // And the map itself
         match self {
-            ParserState::Vendors(m, _, _) => {
+            ParserState::Vendors(m, _, index) => {
+                // The map holds indices into the sorted array, so each vendor is only emitted once
+                for (i, id) in index.vendors.keys().enumerate() {
+                    m.entry(*id, &i.to_string());
+                }
                 writeln!(output, "{};", m.build()).unwrap();
+
+                let vendors: Vec<&str> = index.vendors.values().map(String::as_str).collect();
+                writeln!(output, "{}", VENDORS_PROLOGUE).unwrap();
+                writeln!(output, "&[{}];", vendors.join(",")).unwrap();
+
+                let mut names = Map::<&str>::new();
+                for (name, id) in index.names.iter() {
+                    names.entry(name, &id.to_string());
+                }
+                writeln!(output, "{}", VENDOR_NAME_PROLOGUE).unwrap();
+                writeln!(output, "{};", names.build()).unwrap();
+
+                // Indices into the sorted array, ordered by name and then ID
+                index.sorted_names.sort_unstable();
+                let positions: BTreeMap<&u16, usize> = index
+                    .vendors
+                    .keys()
+                    .enumerate()
+                    .map(|(i, id)| (id, i))
+                    .collect();
+                let by_name: Vec<usize> = index
+                    .sorted_names
+                    .iter()
+                    .map(|(_, id)| positions[id])
+                    .collect();
+                writeln!(output, "{}&{:?};", VENDORS_BY_NAME_PROLOGUE, by_name).unwrap();
+
+                let ids: Vec<&u16> = index.vendors.keys().collect();
+                writeln!(output, "{}&{:?};", VENDOR_IDS_PROLOGUE, ids).unwrap();
+
+                writeln!(output, "{}{};", VENDOR_COUNT_PROLOGUE, ids.len()).unwrap();
+                writeln!(output, "{}{};", DEVICE_COUNT_PROLOGUE, index.device_count).unwrap();
+
+                // Packed VID/PID to the vendor's index in the sorted array and the device's
+                // index in the vendor's devices
+                if cfg_feature("device-map") {
+                    let mut devices = Map::<u32>::new();
+                    for (i, (vid, pids)) in index.device_ids.iter().enumerate() {
+                        for (j, pid) in pids.iter().enumerate() {
+                            let key = u32::from(*vid) << 16 | u32::from(*pid);
+                            devices.entry(key, &format!("({}, {})", i, j));
+                        }
+                    }
+                    writeln!(output, "{}", DEVICE_IDS_PROLOGUE).unwrap();
+                    writeln!(output, "{};", devices.build()).unwrap();
+                }
             }
-            ParserState::Classes(m, _, _) => {
+            ParserState::Classes(m, _, ids) => {
                 writeln!(output, "{};", m.build()).unwrap();
+
+                ids.sort_unstable();
+                writeln!(output, "{}&{:?};", CLASS_IDS_PROLOGUE, ids).unwrap();
             }
             ParserState::AtType(m, _) | ParserState::TerminalType(m, _) => {
                 writeln!(output, "{};", m.build()).unwrap();
@@ -464,7 +641,7 @@ impl ParserState {
         self.finalize(output);
         match self {
             ParserState::Vendors(_, _, _) => {
-                Some(ParserState::Classes(Map::<u8>::new(), None, 0u8))
+                Some(ParserState::Classes(Map::<u8>::new(), None, vec![]))
             }
             ParserState::Classes(_, _, _) => Some(ParserState::AtType(Map::<u16>::new(), None)),
             ParserState::AtType(_, _) => Some(ParserState::HidType(Map::<u8>::new(), None)),
@@ -482,147 +659,214 @@ impl ParserState {
     }
 }
 
-#[allow(clippy::redundant_field_names)]
-fn main() {
-    let out_dir = env::var_os("OUT_DIR").unwrap();
-    let src_path = Path::new("src/usb.ids");
-    let dest_path = Path::new(&out_dir).join("usb_ids.cg.rs");
-    let input = {
-        let f = fs::File::open(src_path).unwrap();
-        BufReader::new(f)
-    };
-    let mut output = {
-        let f = fs::File::create(dest_path).unwrap();
-        BufWriter::new(f)
-    };
-
-    // Parser state machine starts with vendors (first in file)
-    let mut parser_state: ParserState = ParserState::Vendors(Map::<u16>::new(), None, 0u16);
-
-    #[allow(clippy::lines_filter_map_ok)]
-    for line in input.lines().flatten() {
-        // Check for a state change based on the header comments
-        if let Some(next_state) = parser_state.next_from_header(&line, &mut output) {
-            parser_state = next_state;
-        }
-
-        // Process line for current parser
-        parser_state.process(&line);
+/// Adds a class to the class map, unless the class tables are disabled.
+///
+/// The classes are still parsed without the feature, so malformed input is reported either way.
+fn push_class(m: &mut Map<u8>, ids: &mut Vec<u8>, class: &CgClass) {
+    if cfg_feature("classes") {
+        m.entry(class.id, &quote!(#class).to_string());
+        ids.push(class.id);
     }
+}
 
-    // Last call for last parser in file
-    parser_state.finalize(&mut output);
-
-    println!("cargo:rerun-if-changed=build.rs");
-    println!("cargo:rerun-if-changed=src/usb.ids");
+/// Returns whether the crate feature `name` is enabled for this build.
+fn cfg_feature(name: &str) -> bool {
+    let var = format!("CARGO_FEATURE_{}", name.to_uppercase().replace('-', "_"));
+    env::var_os(var).is_some()
 }
 
-mod parser {
-    use std::num::ParseIntError;
-
-    use nom::bytes::complete::{tag, take};
-    use nom::character::complete::{hex_digit1, tab};
-    use nom::combinator::{all_consuming, map_parser, map_res};
-    use nom::sequence::{delimited, terminated};
-    use nom::IResult;
-
-    fn id<T, F>(size: usize, from_str_radix: F) -> impl Fn(&str) -> IResult<&str, T>
-    where
-        F: Fn(&str, u32) -> Result<T, ParseIntError>,
-    {
-        move |input| {
-            map_res(map_parser(take(size), all_consuming(hex_digit1)), |input| {
-                from_str_radix(input, 16)
-            })(input)
-        }
+/// Returns the path of the `usb.ids` to compile: an arbitrary file from `USB_IDS_PATH`
+/// (e.g. the system's copy), a vendored snapshot `src/usb_ids/<name>.ids` selected by
+/// `USB_IDS_SNAPSHOT`, or else the bundled `src/usb.ids`.
+fn database_path() -> PathBuf {
+    if let Some(path) = env::var_os("USB_IDS_PATH") {
+        return PathBuf::from(path);
     }
 
-    pub fn vendor(input: &str) -> IResult<&str, u16> {
-        let id = id(4, u16::from_str_radix);
-        terminated(id, tag("  "))(input)
+    let name = match env::var("USB_IDS_SNAPSHOT") {
+        Ok(name) => name,
+        Err(_) => return PathBuf::from("src/usb.ids"),
+    };
+    let path = Path::new(SNAPSHOTS_DIR).join(format!("{}.ids", name));
+    if !path.is_file() {
+        panic!(
+            "unknown USB_IDS_SNAPSHOT {:?}, available snapshots: {}",
+            name,
+            snapshots().join(", ")
+        );
     }
+    path
+}
 
-    pub fn device(input: &str) -> IResult<&str, u16> {
-        let id = id(4, u16::from_str_radix);
-        delimited(tab, id, tag("  "))(input)
-    }
+/// The directory of the vendored snapshots selectable by `USB_IDS_SNAPSHOT`.
+const SNAPSHOTS_DIR: &str = "src/usb_ids";
+
+/// Lists the names of the vendored snapshots, sorted.
+fn snapshots() -> Vec<String> {
+    let mut names: Vec<_> = fs::read_dir(SNAPSHOTS_DIR)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            if path.extension()? != "ids" {
+                return None;
+            }
+            Some(path.file_stem()?.to_str()?.to_owned())
+        })
+        .collect();
+    names.sort();
+    names
+}
 
-    pub fn interface(input: &str) -> IResult<&str, u8> {
-        let id = id(2, u8::from_str_radix);
-        delimited(tag("\t\t"), id, tag("  "))(input)
-    }
+/// The optional sidecar with metadata that usb.ids itself doesn't carry.
+const VENDOR_META_PATH: &str = "src/vendor_meta.csv";
+
+/// Reads the vendor websites from the sidecar, one `vendor ID,URL` pair per line with
+/// `#` comments. A missing sidecar yields an empty map.
+///
+/// Vendors aren't checked against usb.ids, so the sidecar keeps working with a
+/// database selected by `USB_IDS_PATH` or `USB_IDS_SNAPSHOT`.
+fn vendor_websites(path: &Path) -> Result<Map<u16>, Vec<String>> {
+    let mut websites = Map::<u16>::new();
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(_) => return Ok(websites),
+    };
 
-    pub fn class(input: &str) -> IResult<&str, u8> {
-        let id = id(2, u8::from_str_radix);
-        delimited(tag("C "), id, tag("  "))(input)
-    }
+    let mut seen = BTreeSet::new();
+    let mut errors = Vec::new();
+    for (number, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
 
-    pub fn sub_class(input: &str) -> IResult<&str, u8> {
-        let id = id(2, u8::from_str_radix);
-        delimited(tab, id, tag("  "))(input)
+        let entry = line.split_once(',').and_then(|(id, url)| {
+            let id = u16::from_str_radix(id.trim(), 16).ok()?;
+            let url = url.trim();
+            (!url.is_empty()).then_some((id, url))
+        });
+        match entry {
+            Some((id, url)) if seen.insert(id) => {
+                websites.entry(id, &format!("{:?}", url));
+            }
+            Some(_) => errors.push(format!(
+                "vendor_meta.csv:{}: duplicate vendor ID",
+                number + 1
+            )),
+            None => errors.push(format!(
+                "vendor_meta.csv:{}: invalid entry: {:?}",
+                number + 1,
+                line
+            )),
+        }
     }
 
-    pub fn protocol(input: &str) -> IResult<&str, u8> {
-        let id = id(2, u8::from_str_radix);
-        delimited(tag("\t\t"), id, tag("  "))(input)
+    if errors.is_empty() {
+        Ok(websites)
+    } else {
+        Err(errors)
     }
+}
 
-    pub fn audio_terminal_type(input: &str) -> IResult<&str, u16> {
-        let id = id(4, u16::from_str_radix);
-        delimited(tag("AT "), id, tag("  "))(input)
-    }
+#[allow(clippy::redundant_field_names)]
+fn main() {
+    let out_dir = env::var_os("OUT_DIR").unwrap();
+    let src_path = database_path();
+    let dest_path = Path::new(&out_dir).join("usb_ids.cg.rs");
+    let mut input = {
+        let f = fs::File::open(&src_path)
+            .unwrap_or_else(|e| panic!("couldn't open {}: {}", src_path.display(), e));
+        BufReader::new(f)
+    };
+    let mut output = {
+        let f = fs::File::create(dest_path).unwrap();
+        BufWriter::new(f)
+    };
 
-    pub fn hid_type(input: &str) -> IResult<&str, u8> {
-        let id = id(2, u8::from_str_radix);
-        delimited(tag("HID "), id, tag("  "))(input)
-    }
+    // Parser state machine starts with vendors (first in file)
+    let mut parser_state: ParserState =
+        ParserState::Vendors(Map::<u16>::new(), None, CgVendorIndex::default());
+
+    // Database version and date from the file header
+    let mut db_version = String::new();
+    let mut db_date = String::new();
+
+    // Malformed lines are collected so that they can all be reported at once
+    let mut errors = Vec::new();
+    let mut buf = Vec::new();
+    let mut number = 0;
+
+    loop {
+        buf.clear();
+        if input.read_until(b'\n', &mut buf).unwrap() == 0 {
+            break;
+        }
+        number += 1;
 
-    pub fn hid_item_type(input: &str) -> IResult<&str, u8> {
-        let id = id(2, u8::from_str_radix);
-        delimited(tag("R "), id, tag("  "))(input)
-    }
+        // usb.ids is not guaranteed to be valid UTF-8; keep the line rather than dropping it
+        let line = String::from_utf8_lossy(&buf);
+        let line = parser::line(&line);
 
-    pub fn bias_type(input: &str) -> IResult<&str, u8> {
-        let id = id(1, u8::from_str_radix);
-        delimited(tag("BIAS "), id, tag("  "))(input)
-    }
+        if let Ok((version, _)) = parser::version(line) {
+            db_version = version.trim().into();
+        } else if let Ok((date, _)) = parser::date(line) {
+            db_date = date.trim().into();
+        }
 
-    pub fn phy_type(input: &str) -> IResult<&str, u8> {
-        let id = id(2, u8::from_str_radix);
-        delimited(tag("PHY "), id, tag("  "))(input)
-    }
+        // Check for a state change based on the header comments
+        match parser_state.next_from_header(line, &mut output) {
+            Ok(Some(next_state)) => parser_state = next_state,
+            Ok(None) => {}
+            Err(e) => panic!("usb.ids:{}: {}", number, e),
+        }
 
-    pub fn hut_type(input: &str) -> IResult<&str, u8> {
-        let id = id(2, u8::from_str_radix);
-        delimited(tag("HUT "), id, tag("  "))(input)
+        // Process line for current parser
+        if let Err(e) = parser_state.process(line) {
+            println!(
+                "cargo:warning=malformed entry at usb.ids:{}: {}",
+                number, line
+            );
+            errors.push(format!("usb.ids:{}: {}: {:?}", number, e, line));
+        }
     }
 
-    pub fn hid_usage_name(input: &str) -> IResult<&str, u16> {
-        let id = id(3, u16::from_str_radix);
-        delimited(tab, id, tag("  "))(input)
+    if !errors.is_empty() {
+        panic!(
+            "{} malformed entries in usb.ids:\n{}",
+            errors.len(),
+            errors.join("\n")
+        );
     }
 
-    pub fn language(input: &str) -> IResult<&str, u16> {
-        let id = id(4, u16::from_str_radix);
-        delimited(tag("L "), id, tag("  "))(input)
-    }
+    // Last call for last parser in file
+    parser_state.finalize(&mut output);
 
-    pub fn dialect(input: &str) -> IResult<&str, u8> {
-        let id = id(2, u8::from_str_radix);
-        delimited(tab, id, tag("  "))(input)
-    }
+    writeln!(output, "{}{:?};", DB_VERSION_PROLOGUE, db_version).unwrap();
+    writeln!(output, "{}{:?};", DB_DATE_PROLOGUE, db_date).unwrap();
 
-    pub fn country_code(input: &str) -> IResult<&str, u8> {
-        let id = id(2, u8::from_str_radix);
-        delimited(tag("HCC "), id, tag("  "))(input)
+    let meta_path = Path::new(VENDOR_META_PATH);
+    match vendor_websites(meta_path) {
+        Ok(websites) => {
+            writeln!(output, "{}{};", VENDOR_WEBSITE_PROLOGUE, websites.build()).unwrap()
+        }
+        Err(errors) => panic!(
+            "{} malformed entries in vendor_meta.csv:\n{}",
+            errors.len(),
+            errors.join("\n")
+        ),
     }
 
-    pub fn terminal_type(input: &str) -> IResult<&str, u16> {
-        let id = id(4, u16::from_str_radix);
-        delimited(tag("VT "), id, tag("  "))(input)
-    }
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed={}", src_path.display());
+    println!("cargo:rerun-if-changed={}", meta_path.display());
+    println!("cargo:rerun-if-env-changed=USB_IDS_PATH");
+    println!("cargo:rerun-if-env-changed=USB_IDS_SNAPSHOT");
 }
 
+#[path = "src/parser.rs"]
+mod parser;
+
 impl quote::ToTokens for CgVendor {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
         let CgVendor {
@@ -631,9 +875,14 @@ impl quote::ToTokens for CgVendor {
             devices,
         } = self;
 
-        let devices = devices.iter().map(|CgDevice { id, name, interfaces }| {
+        let devices = sorted_by_id(devices).into_iter().map(|CgDevice { id: device_id, name, interfaces }| {
+            let interfaces = sorted_by_id(interfaces).into_iter().map(|CgInterface { id, name }| {
+                quote! {
+                    Interface { vendor_id: #vendor_id, device_id: #device_id, id: #id, name: #name }
+                }
+            });
             quote!{
-                Device { vendor_id: #vendor_id, id: #id, name: #name, interfaces: &[#(#interfaces),*] }
+                Device { vendor_id: #vendor_id, id: #device_id, name: #name, interfaces: &[#(#interfaces),*] }
             }
         });
         tokens.extend(quote! {
@@ -650,9 +899,14 @@ impl quote::ToTokens for CgClass {
             sub_classes,
         } = self;
 
-        let sub_classes = sub_classes.iter().map(|CgSubClass { id, name, children }| {
+        let sub_classes = sorted_by_id(sub_classes).into_iter().map(|CgSubClass { id: sub_class_id, name, children }| {
+            let protocols = sorted_by_id(children).into_iter().map(|CgProtocol { id, name }| {
+                quote! {
+                    Protocol { class_id: #class_id, sub_class_id: #sub_class_id, id: #id, name: #name }
+                }
+            });
             quote! {
-                SubClass { class_id: #class_id, id: #id, name: #name, protocols: &[#(#children),*] }
+                SubClass { class_id: #class_id, id: #sub_class_id, name: #name, protocols: &[#(#protocols),*] }
             }
         });
         tokens.extend(quote! {
@@ -0,0 +1,1073 @@
+//! Runtime loading of a `usb.ids` file.
+//!
+//! The embedded database is fixed at build time and goes stale between releases.
+//! [`Database`] parses a user-supplied `usb.ids` (e.g. `/usr/share/hwdata/usb.ids`)
+//! into owned vendor and class data, queried with an API that mirrors the static one.
+//!
+//! ```
+//! use usb_ids::Database;
+//!
+//! let input = "1d6b  Linux Foundation\n\t0003  3.0 root hub\n";
+//! let db = Database::parse(input.as_bytes()).unwrap();
+//!
+//! let device = db.device(0x1d6b, 0x0003).unwrap();
+//! assert_eq!(device.name(), "3.0 root hub");
+//! ```
+
+use std::collections::HashMap;
+use std::error;
+use std::fmt;
+use std::io::{self, BufRead};
+use std::ops::ControlFlow;
+
+use crate::{parser, DeviceRef, VendorRef};
+
+/// Errors that can occur while parsing a `usb.ids` file with [`Database::parse`].
+#[derive(Debug)]
+pub enum ParseError {
+    /// Reading the input failed.
+    Io(io::Error),
+    /// A line in the vendor or class sections did not match any entry format.
+    InvalidLine {
+        /// The 1-based line number.
+        line: usize,
+        /// The offending line.
+        content: String,
+    },
+    /// A nested entry appeared without a parent, e.g. a device before any vendor.
+    MissingParent {
+        /// The 1-based line number.
+        line: usize,
+        /// The offending line.
+        content: String,
+    },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Io(e) => write!(f, "failed to read usb.ids: {}", e),
+            ParseError::InvalidLine { line, content } => {
+                write!(f, "malformed entry at usb.ids:{}: {}", line, content)
+            }
+            ParseError::MissingParent { line, content } => {
+                write!(f, "entry without a parent at usb.ids:{}: {}", line, content)
+            }
+        }
+    }
+}
+
+impl error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            ParseError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ParseError {
+    fn from(e: io::Error) -> Self {
+        ParseError::Io(e)
+    }
+}
+
+/// The canonical location of the latest `usb.ids`, as fetched by [`Database::fetch_latest`].
+#[cfg(feature = "online")]
+pub const USB_IDS_URL: &str = "http://www.linux-usb.org/usb.ids";
+
+/// Errors that can occur while fetching a `usb.ids` file with [`Database::fetch_latest`].
+#[cfg(feature = "online")]
+#[derive(Debug)]
+pub enum FetchError {
+    /// The request failed, or the server returned an error status.
+    Request(Box<ureq::Error>),
+    /// The downloaded file could not be read or parsed.
+    Parse(ParseError),
+}
+
+#[cfg(feature = "online")]
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FetchError::Request(e) => write!(f, "failed to fetch usb.ids: {}", e),
+            FetchError::Parse(e) => e.fmt(f),
+        }
+    }
+}
+
+#[cfg(feature = "online")]
+impl error::Error for FetchError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            FetchError::Request(e) => Some(e),
+            FetchError::Parse(e) => Some(e),
+        }
+    }
+}
+
+#[cfg(feature = "online")]
+impl From<ParseError> for FetchError {
+    fn from(e: ParseError) -> Self {
+        FetchError::Parse(e)
+    }
+}
+
+/// A USB database loaded at runtime.
+///
+/// Only the vendor and class sections are loaded; the remaining sections of the
+/// file are skipped.
+///
+/// A database can also be built up entry by entry, starting from the empty
+/// [`Database::default`], e.g. as a fixture in tests:
+///
+/// ```
+/// use usb_ids::Database;
+///
+/// let mut db = Database::default();
+/// db.insert_vendor(0xfffe, "Acme")
+///     .insert_device(0x0001, "Widget")
+///     .insert_interface(0x00, "Widget Control");
+/// db.insert_class(0x03, "Human Interface Device")
+///     .insert_sub_class(0x01, "Boot Interface Subclass")
+///     .insert_protocol(0x02, "Mouse");
+///
+/// assert_eq!(db.device(0xfffe, 0x0001).unwrap().name(), "Widget");
+/// assert_eq!(db.protocol(0x03, 0x01, 0x02).unwrap().name(), "Mouse");
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Database {
+    vendors: HashMap<u16, Vendor>,
+    classes: HashMap<u8, Class>,
+}
+
+/// The prefixes of the top-level lines in the sections after the classes, which
+/// aren't loaded into a [`Database`].
+const OTHER_SECTION_PREFIXES: &[&str] = &[
+    "AT ", "HID ", "R ", "BIAS ", "PHY ", "HUT ", "L ", "HCC ", "VT ",
+];
+
+/// The section of the file currently being parsed.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Section {
+    Vendors,
+    Classes,
+    Other,
+}
+
+impl Database {
+    /// Parses a database from `reader`, which must contain a file in the `usb.ids` format.
+    ///
+    /// The file is not guaranteed to be valid UTF-8, so invalid sequences in names
+    /// are replaced with `U+FFFD`.
+    pub fn parse(reader: impl BufRead) -> Result<Database, ParseError> {
+        let mut db = Database::default();
+
+        // Every entry's parents are yielded before it, so they can be unwrapped
+        parse_stream(reader, |entry| {
+            match entry {
+                ParsedEntry::Vendor { id, name } => {
+                    db.insert_vendor(id, name);
+                }
+                ParsedEntry::Device {
+                    vendor_id,
+                    id,
+                    name,
+                } => {
+                    db.vendor_mut(vendor_id).unwrap().insert_device(id, name);
+                }
+                ParsedEntry::Interface {
+                    vendor_id,
+                    device_id,
+                    id,
+                    name,
+                } => {
+                    db.vendor_mut(vendor_id)
+                        .and_then(|v| v.device_mut(device_id))
+                        .unwrap()
+                        .insert_interface(id, name);
+                }
+                ParsedEntry::Class { id, name } => {
+                    db.insert_class(id, name);
+                }
+                ParsedEntry::SubClass { class_id, id, name } => {
+                    db.class_mut(class_id).unwrap().insert_sub_class(id, name);
+                }
+                ParsedEntry::Protocol {
+                    class_id,
+                    sub_class_id,
+                    id,
+                    name,
+                } => {
+                    db.class_mut(class_id)
+                        .and_then(|c| c.sub_class_mut(sub_class_id))
+                        .unwrap()
+                        .insert_protocol(id, name);
+                }
+            }
+
+            ControlFlow::Continue(())
+        })?;
+
+        Ok(db)
+    }
+
+    /// Downloads the latest `usb.ids` from [`USB_IDS_URL`] and parses it, as with
+    /// [`Database::parse`].
+    ///
+    /// This is opt-in, with the `online` feature, and only returns a new database:
+    /// the data embedded at build time is unaffected.
+    ///
+    /// ```no_run
+    /// use usb_ids::Database;
+    ///
+    /// let db = Database::fetch_latest().unwrap();
+    /// println!("{} vendors", db.vendors().count());
+    /// ```
+    #[cfg(feature = "online")]
+    pub fn fetch_latest() -> Result<Database, FetchError> {
+        let response = ureq::get(USB_IDS_URL)
+            .call()
+            .map_err(|e| FetchError::Request(Box::new(e)))?;
+
+        Ok(Database::parse(io::BufReader::new(response.into_reader()))?)
+    }
+
+    /// Adds a vendor, or renames the vendor if one with the ID already exists, and
+    /// returns it so that devices can be added to it.
+    pub fn insert_vendor(&mut self, id: u16, name: impl Into<String>) -> &mut Vendor {
+        let vendor = self.vendors.entry(id).or_insert_with(|| Vendor {
+            id,
+            name: String::new(),
+            devices: vec![],
+        });
+        vendor.name = name.into();
+        vendor
+    }
+
+    /// Adds a class, or renames the class if one with the ID already exists, and
+    /// returns it so that subclasses can be added to it.
+    pub fn insert_class(&mut self, id: u8, name: impl Into<String>) -> &mut Class {
+        let class = self.classes.entry(id).or_insert_with(|| Class {
+            id,
+            name: String::new(),
+            sub_classes: vec![],
+        });
+        class.name = name.into();
+        class
+    }
+
+    /// Returns an iterator over all vendors in the database.
+    pub fn vendors(&self) -> impl Iterator<Item = &Vendor> {
+        self.vendors.values()
+    }
+
+    /// Returns an iterator over all classes in the database.
+    pub fn classes(&self) -> impl Iterator<Item = &Class> {
+        self.classes.values()
+    }
+
+    /// Returns the [`Vendor`] with the given ID, or `None` if no such vendor exists.
+    pub fn vendor(&self, id: u16) -> Option<&Vendor> {
+        self.vendors.get(&id)
+    }
+
+    /// Returns the [`Device`] with the given vendor and product IDs, or `None` if
+    /// no such device exists.
+    pub fn device(&self, vid: u16, pid: u16) -> Option<&Device> {
+        self.vendor(vid)?.device(pid)
+    }
+
+    /// Returns the [`Class`] with the given ID, or `None` if no such class exists.
+    pub fn class(&self, id: u8) -> Option<&Class> {
+        self.classes.get(&id)
+    }
+
+    fn vendor_mut(&mut self, id: u16) -> Option<&mut Vendor> {
+        self.vendors.get_mut(&id)
+    }
+
+    fn class_mut(&mut self, id: u8) -> Option<&mut Class> {
+        self.classes.get_mut(&id)
+    }
+
+    /// Returns the [`SubClass`] with the given class and subclass IDs, or `None`
+    /// if no such subclass exists.
+    pub fn sub_class(&self, class_id: u8, id: u8) -> Option<&SubClass> {
+        self.class(class_id)?.sub_class(id)
+    }
+
+    /// Returns the [`Protocol`] with the given class, subclass, and protocol IDs,
+    /// or `None` if no such protocol exists.
+    pub fn protocol(&self, class_id: u8, subclass_id: u8, id: u8) -> Option<&Protocol> {
+        self.sub_class(class_id, subclass_id)?.protocol(id)
+    }
+}
+
+/// A vendor in a runtime [`Database`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Vendor {
+    id: u16,
+    name: String,
+    devices: Vec<Device>,
+}
+
+impl Vendor {
+    /// Returns the vendor's ID.
+    pub fn id(&self) -> u16 {
+        self.id
+    }
+
+    /// Returns the vendor's name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns an iterator over the vendor's [`Device`]s.
+    pub fn devices(&self) -> impl Iterator<Item = &Device> {
+        self.devices.iter()
+    }
+
+    /// Returns the vendor's [`Device`] with the given product ID, if any.
+    pub fn device(&self, pid: u16) -> Option<&Device> {
+        self.devices
+            .binary_search_by_key(&pid, |d| d.id)
+            .ok()
+            .map(|i| &self.devices[i])
+    }
+
+    fn device_mut(&mut self, pid: u16) -> Option<&mut Device> {
+        self.devices
+            .binary_search_by_key(&pid, |d| d.id)
+            .ok()
+            .map(move |i| &mut self.devices[i])
+    }
+
+    /// Adds a device, or renames the device if one with the ID already exists, and
+    /// returns it so that interfaces can be added to it.
+    pub fn insert_device(&mut self, id: u16, name: impl Into<String>) -> &mut Device {
+        let vendor_id = self.id;
+        let i = entry_index(
+            &mut self.devices,
+            id,
+            |d| d.id,
+            || Device {
+                vendor_id,
+                id,
+                name: String::new(),
+                interfaces: vec![],
+            },
+        );
+        let device = &mut self.devices[i];
+        device.name = name.into();
+        device
+    }
+}
+
+impl VendorRef for Vendor {
+    fn id(&self) -> u16 {
+        self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// A device in a runtime [`Database`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Device {
+    vendor_id: u16,
+    id: u16,
+    name: String,
+    interfaces: Vec<Interface>,
+}
+
+impl Device {
+    /// Returns a tuple of (vendor id, device/"product" id) for this device.
+    pub fn as_vid_pid(&self) -> (u16, u16) {
+        (self.vendor_id, self.id)
+    }
+
+    /// Returns the device's ID.
+    pub fn id(&self) -> u16 {
+        self.id
+    }
+
+    /// Returns the device's name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns an iterator over the device's [`Interface`]s.
+    pub fn interfaces(&self) -> impl Iterator<Item = &Interface> {
+        self.interfaces.iter()
+    }
+
+    /// Adds an interface, or renames the interface if one with the ID already exists.
+    pub fn insert_interface(&mut self, id: u8, name: impl Into<String>) -> &mut Interface {
+        let i = entry_index(
+            &mut self.interfaces,
+            id,
+            |i| i.id,
+            || Interface {
+                id,
+                name: String::new(),
+            },
+        );
+        let interface = &mut self.interfaces[i];
+        interface.name = name.into();
+        interface
+    }
+}
+
+impl DeviceRef for Device {
+    fn as_vid_pid(&self) -> (u16, u16) {
+        (self.vendor_id, self.id)
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// An interface in a runtime [`Database`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Interface {
+    id: u8,
+    name: String,
+}
+
+impl Interface {
+    /// Returns the interface's ID.
+    pub fn id(&self) -> u8 {
+        self.id
+    }
+
+    /// Returns the interface's name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// A device class in a runtime [`Database`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Class {
+    id: u8,
+    name: String,
+    sub_classes: Vec<SubClass>,
+}
+
+impl Class {
+    /// Returns the class's ID.
+    pub fn id(&self) -> u8 {
+        self.id
+    }
+
+    /// Returns the class's name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns an iterator over the class's [`SubClass`]es.
+    pub fn sub_classes(&self) -> impl Iterator<Item = &SubClass> {
+        self.sub_classes.iter()
+    }
+
+    /// Returns the class's [`SubClass`] with the given ID, if any.
+    pub fn sub_class(&self, id: u8) -> Option<&SubClass> {
+        self.sub_classes
+            .binary_search_by_key(&id, |s| s.id)
+            .ok()
+            .map(|i| &self.sub_classes[i])
+    }
+
+    fn sub_class_mut(&mut self, id: u8) -> Option<&mut SubClass> {
+        self.sub_classes
+            .binary_search_by_key(&id, |s| s.id)
+            .ok()
+            .map(move |i| &mut self.sub_classes[i])
+    }
+
+    /// Adds a subclass, or renames the subclass if one with the ID already exists,
+    /// and returns it so that protocols can be added to it.
+    pub fn insert_sub_class(&mut self, id: u8, name: impl Into<String>) -> &mut SubClass {
+        let class_id = self.id;
+        let i = entry_index(
+            &mut self.sub_classes,
+            id,
+            |s| s.id,
+            || SubClass {
+                class_id,
+                id,
+                name: String::new(),
+                protocols: vec![],
+            },
+        );
+        let sub_class = &mut self.sub_classes[i];
+        sub_class.name = name.into();
+        sub_class
+    }
+}
+
+/// A class subclass in a runtime [`Database`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SubClass {
+    class_id: u8,
+    id: u8,
+    name: String,
+    protocols: Vec<Protocol>,
+}
+
+impl SubClass {
+    /// Returns a tuple of (class id, subclass id) for this subclass.
+    pub fn as_cid_scid(&self) -> (u8, u8) {
+        (self.class_id, self.id)
+    }
+
+    /// Returns the subclass' ID.
+    pub fn id(&self) -> u8 {
+        self.id
+    }
+
+    /// Returns the subclass' name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns an iterator over the subclass' [`Protocol`]s.
+    pub fn protocols(&self) -> impl Iterator<Item = &Protocol> {
+        self.protocols.iter()
+    }
+
+    /// Returns the subclass' [`Protocol`] with the given ID, if any.
+    pub fn protocol(&self, id: u8) -> Option<&Protocol> {
+        self.protocols
+            .binary_search_by_key(&id, |p| p.id)
+            .ok()
+            .map(|i| &self.protocols[i])
+    }
+
+    /// Adds a protocol, or renames the protocol if one with the ID already exists.
+    pub fn insert_protocol(&mut self, id: u8, name: impl Into<String>) -> &mut Protocol {
+        let i = entry_index(
+            &mut self.protocols,
+            id,
+            |p| p.id,
+            || Protocol {
+                id,
+                name: String::new(),
+            },
+        );
+        let protocol = &mut self.protocols[i];
+        protocol.name = name.into();
+        protocol
+    }
+}
+
+/// A subclass protocol in a runtime [`Database`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Protocol {
+    id: u8,
+    name: String,
+}
+
+impl Protocol {
+    /// Returns the protocol's ID.
+    pub fn id(&self) -> u8 {
+        self.id
+    }
+
+    /// Returns the protocol's name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// An entry in the vendor or class sections of a `usb.ids` file, as yielded by
+/// [`parse_stream`]. Nested entries carry the IDs of their parents.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParsedEntry<'a> {
+    /// A vendor.
+    Vendor {
+        /// The vendor's ID.
+        id: u16,
+        /// The vendor's name.
+        name: &'a str,
+    },
+    /// A device of the most recent vendor.
+    Device {
+        /// The ID of the device's vendor.
+        vendor_id: u16,
+        /// The device's ID.
+        id: u16,
+        /// The device's name.
+        name: &'a str,
+    },
+    /// An interface of the most recent device.
+    Interface {
+        /// The ID of the interface's vendor.
+        vendor_id: u16,
+        /// The ID of the interface's device.
+        device_id: u16,
+        /// The interface's ID.
+        id: u8,
+        /// The interface's name.
+        name: &'a str,
+    },
+    /// A device class.
+    Class {
+        /// The class's ID.
+        id: u8,
+        /// The class's name.
+        name: &'a str,
+    },
+    /// A subclass of the most recent class.
+    SubClass {
+        /// The ID of the subclass' class.
+        class_id: u8,
+        /// The subclass' ID.
+        id: u8,
+        /// The subclass' name.
+        name: &'a str,
+    },
+    /// A protocol of the most recent subclass.
+    Protocol {
+        /// The ID of the protocol's class.
+        class_id: u8,
+        /// The ID of the protocol's subclass.
+        sub_class_id: u8,
+        /// The protocol's ID.
+        id: u8,
+        /// The protocol's name.
+        name: &'a str,
+    },
+}
+
+/// Parses the vendor and class sections of a `usb.ids` file from `reader`, passing
+/// each entry to `f` as it is read, without building a [`Database`].
+///
+/// Parsing stops early if `f` returns [`ControlFlow::Break`]. Entries are yielded
+/// in file order, and a nested entry is only yielded after its parents.
+///
+/// ```
+/// use std::ops::ControlFlow;
+/// use usb_ids::database::{parse_stream, ParsedEntry};
+///
+/// let input = "1d6b  Linux Foundation\n\t0002  2.0 root hub\n\t0003  3.0 root hub\n";
+/// let mut found = None;
+/// parse_stream(input.as_bytes(), |entry| match entry {
+///     ParsedEntry::Device { vendor_id: 0x1d6b, id: 0x0002, name, .. } => {
+///         found = Some(name.to_owned());
+///         ControlFlow::Break(())
+///     }
+///     _ => ControlFlow::Continue(()),
+/// })
+/// .unwrap();
+/// assert_eq!(found.as_deref(), Some("2.0 root hub"));
+/// ```
+pub fn parse_stream(
+    mut reader: impl BufRead,
+    mut f: impl FnMut(ParsedEntry<'_>) -> ControlFlow<()>,
+) -> Result<(), ParseError> {
+    let mut section = Section::Vendors;
+    // The most recent entry at each level, which nested entries belong to
+    let mut curr_vendor: Option<u16> = None;
+    let mut curr_device: Option<u16> = None;
+    let mut curr_class: Option<u8> = None;
+    let mut curr_sub_class: Option<u8> = None;
+    let mut buf = Vec::new();
+    let mut number = 0;
+
+    loop {
+        buf.clear();
+        if reader.read_until(b'\n', &mut buf)? == 0 {
+            break;
+        }
+        number += 1;
+
+        let line = String::from_utf8_lossy(&buf);
+        let line = parser::line(&line);
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let invalid = || ParseError::InvalidLine {
+            line: number,
+            content: line.into(),
+        };
+        let missing_parent = || ParseError::MissingParent {
+            line: number,
+            content: line.into(),
+        };
+
+        let entry = if !line.starts_with('\t') {
+            // Top-level lines start a new entry, or move us into the next section.
+            curr_vendor = None;
+            curr_device = None;
+            curr_class = None;
+            curr_sub_class = None;
+
+            if let (Section::Vendors, Ok((name, id))) = (section, parser::vendor(line)) {
+                curr_vendor = Some(id);
+                ParsedEntry::Vendor { id, name }
+            } else if let (Section::Vendors | Section::Classes, Ok((name, id))) =
+                (section, parser::class(line))
+            {
+                section = Section::Classes;
+                curr_class = Some(id);
+                ParsedEntry::Class { id, name }
+            } else if OTHER_SECTION_PREFIXES.iter().any(|p| line.starts_with(p)) {
+                section = Section::Other;
+                continue;
+            } else {
+                return Err(invalid());
+            }
+        } else {
+            match section {
+                Section::Vendors => {
+                    let vendor_id = curr_vendor.ok_or_else(missing_parent)?;
+                    if let Ok((name, id)) = parser::device(line) {
+                        curr_device = Some(id);
+                        ParsedEntry::Device {
+                            vendor_id,
+                            id,
+                            name,
+                        }
+                    } else if let Ok((name, id)) = parser::interface(line) {
+                        ParsedEntry::Interface {
+                            vendor_id,
+                            device_id: curr_device.ok_or_else(missing_parent)?,
+                            id,
+                            name,
+                        }
+                    } else {
+                        return Err(invalid());
+                    }
+                }
+                Section::Classes => {
+                    let class_id = curr_class.ok_or_else(missing_parent)?;
+                    if let Ok((name, id)) = parser::sub_class(line) {
+                        curr_sub_class = Some(id);
+                        ParsedEntry::SubClass { class_id, id, name }
+                    } else if let Ok((name, id)) = parser::protocol(line) {
+                        ParsedEntry::Protocol {
+                            class_id,
+                            sub_class_id: curr_sub_class.ok_or_else(missing_parent)?,
+                            id,
+                            name,
+                        }
+                    } else {
+                        return Err(invalid());
+                    }
+                }
+                Section::Other => continue,
+            }
+        };
+
+        if f(entry).is_break() {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the index of the entry with the given ID in `entries`, which are sorted by
+/// ID, first inserting `new()` in order if there is no such entry.
+fn entry_index<T, K: Ord>(
+    entries: &mut Vec<T>,
+    id: K,
+    key: impl Fn(&T) -> K,
+    new: impl FnOnce() -> T,
+) -> usize {
+    match entries.binary_search_by(|e| key(e).cmp(&id)) {
+        Ok(i) => i,
+        Err(i) => {
+            entries.insert(i, new());
+            i
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Vendors;
+
+    #[test]
+    fn test_parse() {
+        let input = "\
+# comment
+1d6b  Linux Foundation
+\t0002  2.0 root hub
+\t0001  1.1 root hub
+\t\t00  Root
+ffee  Last Vendor
+
+C 03  Human Interface Device
+\t01  Boot Interface Subclass
+\t\t01  Keyboard
+\t\t02  Mouse
+
+HUT 01  Generic Desktop Controls
+\t002  Mouse
+";
+        let db = Database::parse(input.as_bytes()).unwrap();
+
+        assert_eq!(db.vendors().count(), 2);
+        assert_eq!(db.vendor(0x1d6b).unwrap().name(), "Linux Foundation");
+        assert_eq!(db.vendor(0xffee).unwrap().devices().count(), 0);
+
+        let device = db.device(0x1d6b, 0x0002).unwrap();
+        assert_eq!(device.name(), "2.0 root hub");
+        assert_eq!(device.as_vid_pid(), (0x1d6b, 0x0002));
+        assert_eq!(device.interfaces().count(), 0);
+
+        let device = db.device(0x1d6b, 0x0001).unwrap();
+        assert_eq!(device.interfaces().next().unwrap().name(), "Root");
+
+        assert_eq!(db.classes().count(), 1);
+        assert_eq!(
+            db.sub_class(0x03, 0x01).unwrap().as_cid_scid(),
+            (0x03, 0x01)
+        );
+        assert_eq!(db.protocol(0x03, 0x01, 0x02).unwrap().name(), "Mouse");
+        assert!(db.protocol(0x03, 0x01, 0x03).is_none());
+    }
+
+    #[test]
+    fn test_parse_hash_in_name() {
+        let input = "# comment\nfffe  USB# Adapter\n\t0001  Port #1\n";
+        let db = Database::parse(input.as_bytes()).unwrap();
+
+        assert_eq!(db.vendor(0xfffe).unwrap().name(), "USB# Adapter");
+        assert_eq!(db.device(0xfffe, 0x0001).unwrap().name(), "Port #1");
+    }
+
+    #[test]
+    fn test_parse_crlf() {
+        let input = "1d6b  Linux Foundation\r\n\t0002  2.0 root hub\r\n";
+        let db = Database::parse(input.as_bytes()).unwrap();
+
+        assert_eq!(db.vendor(0x1d6b).unwrap().name(), "Linux Foundation");
+        assert_eq!(db.device(0x1d6b, 0x0002).unwrap().name(), "2.0 root hub");
+
+        let db = Database::parse("1d6b  Linux Foundation \n".as_bytes()).unwrap();
+        assert_eq!(db.vendor(0x1d6b).unwrap().name(), "Linux Foundation");
+    }
+
+    #[test]
+    fn test_insert() {
+        let mut db = Database::default();
+        assert_eq!(db.vendors().count(), 0);
+        assert_eq!(db.classes().count(), 0);
+
+        let vendor = db.insert_vendor(0xfffe, "Acme");
+        vendor.insert_device(0x0002, "Second");
+        vendor
+            .insert_device(0x0001, "First")
+            .insert_interface(0x00, "Control");
+        db.insert_vendor(0xfffe, "Acme Corp");
+
+        let vendor = db.vendor(0xfffe).unwrap();
+        assert_eq!(vendor.name(), "Acme Corp");
+        let ids: Vec<_> = vendor.devices().map(|d| d.id()).collect();
+        assert_eq!(ids, vec![0x0001, 0x0002]);
+        let device = db.device(0xfffe, 0x0001).unwrap();
+        assert_eq!(device.as_vid_pid(), (0xfffe, 0x0001));
+        assert_eq!(device.interfaces().next().unwrap().name(), "Control");
+        assert_eq!(db.device(0xfffe, 0x0002).unwrap().name(), "Second");
+
+        db.insert_class(0x03, "HID")
+            .insert_sub_class(0x01, "Boot")
+            .insert_protocol(0x02, "Mouse");
+        db.insert_class(0x03, "Human Interface Device")
+            .insert_sub_class(0x01, "Boot")
+            .insert_protocol(0x01, "Keyboard");
+        assert_eq!(db.class(0x03).unwrap().name(), "Human Interface Device");
+        assert_eq!(
+            db.sub_class(0x03, 0x01).unwrap().as_cid_scid(),
+            (0x03, 0x01)
+        );
+        assert_eq!(db.protocol(0x03, 0x01, 0x01).unwrap().name(), "Keyboard");
+        assert_eq!(db.protocol(0x03, 0x01, 0x02).unwrap().name(), "Mouse");
+
+        let input = "fffe  Acme Corp\n\t0001  First\n\t\t00  Control\n\t0002  Second\n\nC 03  Human Interface Device\n\t01  Boot\n\t\t01  Keyboard\n\t\t02  Mouse\n";
+        assert_eq!(db, Database::parse(input.as_bytes()).unwrap());
+    }
+
+    #[test]
+    fn test_parse_stream() {
+        let input = "1d6b  Linux Foundation\n\t0001  1.1 root hub\n\t\t00  Root\n\nC 03  HID\n\t01  Boot\n\t\t02  Mouse\n";
+        let mut entries = vec![];
+        parse_stream(input.as_bytes(), |entry| {
+            entries.push(format!("{:?}", entry));
+            ControlFlow::Continue(())
+        })
+        .unwrap();
+        assert_eq!(
+            entries,
+            vec![
+                r#"Vendor { id: 7531, name: "Linux Foundation" }"#,
+                r#"Device { vendor_id: 7531, id: 1, name: "1.1 root hub" }"#,
+                r#"Interface { vendor_id: 7531, device_id: 1, id: 0, name: "Root" }"#,
+                r#"Class { id: 3, name: "HID" }"#,
+                r#"SubClass { class_id: 3, id: 1, name: "Boot" }"#,
+                r#"Protocol { class_id: 3, sub_class_id: 1, id: 2, name: "Mouse" }"#,
+            ]
+        );
+
+        // stops at the first vendor, without reading on to the malformed line
+        let mut count = 0;
+        parse_stream("0001  A\n0002  B\nbad\n".as_bytes(), |_| {
+            count += 1;
+            ControlFlow::Break(())
+        })
+        .unwrap();
+        assert_eq!(count, 1);
+
+        let err = parse_stream("\t0001  Orphan\n".as_bytes(), |_| ControlFlow::Continue(()));
+        assert!(matches!(
+            err,
+            Err(ParseError::MissingParent { line: 1, .. })
+        ));
+    }
+
+    /// Interface and protocol lines share the same indentation and format, so only
+    /// the enclosing section tells them apart.
+    #[test]
+    fn test_parse_interface_or_protocol() {
+        let input = "1d6b  Linux Foundation\n\t0003  3.0 root hub\n\t\t01  Same\n\nC 09  Hub\n\t00  Unused\n\t\t01  Same\n";
+        let mut entries = vec![];
+        parse_stream(input.as_bytes(), |entry| {
+            if let ParsedEntry::Interface { .. } | ParsedEntry::Protocol { .. } = entry {
+                entries.push(format!("{:?}", entry));
+            }
+            ControlFlow::Continue(())
+        })
+        .unwrap();
+        assert_eq!(
+            entries,
+            vec![
+                r#"Interface { vendor_id: 7531, device_id: 3, id: 1, name: "Same" }"#,
+                r#"Protocol { class_id: 9, sub_class_id: 0, id: 1, name: "Same" }"#,
+            ]
+        );
+
+        let db = Database::parse(input.as_bytes()).unwrap();
+        let device = db.device(0x1d6b, 0x0003).unwrap();
+        assert_eq!(
+            device.interfaces().map(|i| i.name()).collect::<Vec<_>>(),
+            ["Same"]
+        );
+        assert_eq!(db.protocol(0x09, 0x00, 0x01).unwrap().name(), "Same");
+        assert_eq!(db.sub_class(0x09, 0x00).unwrap().protocols().count(), 1);
+    }
+
+    #[test]
+    fn test_refs() {
+        fn names(vendor: &impl VendorRef, device: &impl DeviceRef) -> String {
+            format!("{} / {}", vendor.name(), device.name())
+        }
+
+        let db = Database::parse(&include_bytes!("usb.ids")[..]).unwrap();
+        let runtime = names(
+            db.vendor(0x1d6b).unwrap(),
+            db.device(0x1d6b, 0x0003).unwrap(),
+        );
+        let device = crate::Device::from_vid_pid(0x1d6b, 0x0003).unwrap();
+        assert_eq!(runtime, names(device.vendor(), device));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = Database::parse("\t0001  Orphan\n".as_bytes()).unwrap_err();
+        assert!(matches!(err, ParseError::MissingParent { line: 1, .. }));
+
+        let err =
+            Database::parse("1d6b  Linux Foundation\n\t\t01  Orphan\n".as_bytes()).unwrap_err();
+        assert!(matches!(err, ParseError::MissingParent { line: 2, .. }));
+
+        // an interface must not attach to a device of the previous vendor
+        let input = "0001  A\n\t0001  Device\n0002  B\n\t\t01  Orphan\n\t0001  Device\n";
+        let err = Database::parse(input.as_bytes()).unwrap_err();
+        assert!(matches!(err, ParseError::MissingParent { line: 4, .. }));
+
+        let err = Database::parse("1d6b  Linux Foundation\n\t001  Bad\n".as_bytes()).unwrap_err();
+        assert!(matches!(err, ParseError::InvalidLine { line: 2, .. }));
+        assert_eq!(err.to_string(), "malformed entry at usb.ids:2: \t001  Bad");
+
+        // a malformed top-level line must not end the parse early
+        let input = "046d Logitech\n1d6b  Linux Foundation\n\t0003  3.0 root hub\nC 09  Hub\n";
+        let err = Database::parse(input.as_bytes()).unwrap_err();
+        assert!(matches!(err, ParseError::InvalidLine { line: 1, .. }));
+
+        let input = "1d6b  Linux Foundation\nC 09  Hub\nXYZ 01  Unknown section\n";
+        let err = Database::parse(input.as_bytes()).unwrap_err();
+        assert!(matches!(err, ParseError::InvalidLine { line: 3, .. }));
+    }
+
+    #[test]
+    fn test_parse_bundled() {
+        let db = Database::parse(&include_bytes!("usb.ids")[..]).unwrap();
+
+        assert_eq!(db.vendors().count(), Vendors::len());
+        for vendor in Vendors::iter() {
+            let runtime = db.vendor(vendor.id()).unwrap();
+            assert_eq!(runtime.name(), vendor.name());
+            assert_eq!(runtime.devices().count(), vendor.devices().count());
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "classes")]
+    fn test_parse_bundled_classes() {
+        use crate::{Class, Classes, FromId};
+
+        let db = Database::parse(&include_bytes!("usb.ids")[..]).unwrap();
+
+        assert_eq!(db.classes().count(), Classes::len());
+        let class = Class::from_id(0x03).unwrap();
+        assert_eq!(db.class(0x03).unwrap().name(), class.name());
+    }
+
+    #[test]
+    fn test_parse_written() {
+        let mut out = Vec::new();
+        crate::write_database(&mut out).unwrap();
+        let written = Database::parse(&out[..]).unwrap();
+        let bundled = Database::parse(&include_bytes!("usb.ids")[..]).unwrap();
+
+        assert_eq!(written.vendors().count(), bundled.vendors().count());
+        for vendor in bundled.vendors() {
+            let other = written.vendor(vendor.id()).unwrap();
+            assert_eq!(other.name(), vendor.name());
+            for device in vendor.devices() {
+                let (vid, pid) = device.as_vid_pid();
+                assert_eq!(written.device(vid, pid).unwrap().name(), device.name());
+            }
+            assert_eq!(other.devices().count(), vendor.devices().count());
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "classes")]
+    fn test_parse_written_classes() {
+        let mut out = Vec::new();
+        crate::write_database(&mut out).unwrap();
+        let written = Database::parse(&out[..]).unwrap();
+        let bundled = Database::parse(&include_bytes!("usb.ids")[..]).unwrap();
+
+        assert_eq!(written.classes().count(), bundled.classes().count());
+        for class in bundled.classes() {
+            assert_eq!(written.class(class.id()).unwrap().name(), class.name());
+            for sub_class in class.sub_classes() {
+                let (cid, scid) = sub_class.as_cid_scid();
+                let other = written.sub_class(cid, scid).unwrap();
+                assert_eq!(other.name(), sub_class.name());
+                assert_eq!(other.protocols().count(), sub_class.protocols().count());
+            }
+        }
+    }
+}
@@ -0,0 +1,125 @@
+//! `Deserialize` implementations for the database types.
+//!
+//! Only the IDs are read back; the entity is then resolved against the embedded
+//! database so that deserialized values are always consistent with it.
+
+use serde::de::{Deserialize, Deserializer, Error};
+
+use crate::{Class, Device, FromId, Interface, Protocol, SubClass, Vendor};
+
+#[derive(serde::Deserialize)]
+struct VendorIds {
+    id: u16,
+}
+
+#[derive(serde::Deserialize)]
+struct DeviceIds {
+    vendor_id: u16,
+    id: u16,
+}
+
+#[derive(serde::Deserialize)]
+struct InterfaceIds {
+    vendor_id: u16,
+    device_id: u16,
+    id: u8,
+}
+
+#[derive(serde::Deserialize)]
+struct ClassIds {
+    id: u8,
+}
+
+#[derive(serde::Deserialize)]
+struct SubClassIds {
+    class_id: u8,
+    id: u8,
+}
+
+#[derive(serde::Deserialize)]
+struct ProtocolIds {
+    class_id: u8,
+    sub_class_id: u8,
+    id: u8,
+}
+
+impl<'de> Deserialize<'de> for Vendor {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let VendorIds { id } = VendorIds::deserialize(deserializer)?;
+
+        Vendor::from_id(id)
+            .copied()
+            .ok_or_else(|| D::Error::custom(format_args!("unknown vendor {:04x}", id)))
+    }
+}
+
+impl<'de> Deserialize<'de> for Device {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let DeviceIds { vendor_id, id } = DeviceIds::deserialize(deserializer)?;
+
+        Device::from_vid_pid(vendor_id, id).copied().ok_or_else(|| {
+            D::Error::custom(format_args!("unknown device {:04x}:{:04x}", vendor_id, id))
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for Class {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let ClassIds { id } = ClassIds::deserialize(deserializer)?;
+
+        Class::from_id(id)
+            .copied()
+            .ok_or_else(|| D::Error::custom(format_args!("unknown class {:02x}", id)))
+    }
+}
+
+impl<'de> Deserialize<'de> for Interface {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let InterfaceIds {
+            vendor_id,
+            device_id,
+            id,
+        } = InterfaceIds::deserialize(deserializer)?;
+
+        Device::from_vid_pid(vendor_id, device_id)
+            .and_then(|device| device.interface(id))
+            .copied()
+            .ok_or_else(|| {
+                D::Error::custom(format_args!(
+                    "unknown interface {:04x}:{:04x}:{:02x}",
+                    vendor_id, device_id, id
+                ))
+            })
+    }
+}
+
+impl<'de> Deserialize<'de> for SubClass {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let SubClassIds { class_id, id } = SubClassIds::deserialize(deserializer)?;
+
+        SubClass::from_cid_scid(class_id, id)
+            .copied()
+            .ok_or_else(|| {
+                D::Error::custom(format_args!("unknown subclass {:02x}:{:02x}", class_id, id))
+            })
+    }
+}
+
+impl<'de> Deserialize<'de> for Protocol {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let ProtocolIds {
+            class_id,
+            sub_class_id,
+            id,
+        } = ProtocolIds::deserialize(deserializer)?;
+
+        Protocol::from_cid_scid_pid(class_id, sub_class_id, id)
+            .copied()
+            .ok_or_else(|| {
+                D::Error::custom(format_args!(
+                    "unknown protocol {:02x}:{:02x}:{:02x}",
+                    class_id, sub_class_id, id
+                ))
+            })
+    }
+}
@@ -0,0 +1,110 @@
+//! Conversions from the descriptor types of other USB libraries.
+//!
+//! Each integration is behind a feature of the same name, so the crate itself
+//! never depends on a USB stack.
+
+#[cfg(any(feature = "rusb", feature = "nusb"))]
+use crate::{Device, FromId, Vendor};
+
+#[cfg(feature = "nusb")]
+use crate::Class;
+
+#[cfg(feature = "rusb")]
+impl Vendor {
+    /// Returns the [`Vendor`] for a [`rusb::DeviceDescriptor`]'s vendor ID, or `None`
+    /// if no such vendor exists in the DB.
+    ///
+    /// ```no_run
+    /// use usb_ids::Vendor;
+    ///
+    /// for device in rusb::devices().unwrap().iter() {
+    ///     let descriptor = device.device_descriptor().unwrap();
+    ///     if let Some(vendor) = Vendor::from_device_descriptor(&descriptor) {
+    ///         println!("vendor: {}", vendor.name());
+    ///     }
+    /// }
+    /// ```
+    pub fn from_device_descriptor(descriptor: &rusb::DeviceDescriptor) -> Option<&'static Vendor> {
+        Vendor::from_id(descriptor.vendor_id())
+    }
+}
+
+#[cfg(feature = "rusb")]
+impl Device {
+    /// Returns the [`Device`] for a [`rusb::DeviceDescriptor`]'s vendor and product
+    /// IDs, or `None` if no such device exists in the DB.
+    ///
+    /// ```no_run
+    /// use usb_ids::Device;
+    ///
+    /// for device in rusb::devices().unwrap().iter() {
+    ///     let descriptor = device.device_descriptor().unwrap();
+    ///     if let Some(device) = Device::from_device_descriptor(&descriptor) {
+    ///         println!("device: {}", device.name());
+    ///     }
+    /// }
+    /// ```
+    pub fn from_device_descriptor(descriptor: &rusb::DeviceDescriptor) -> Option<&'static Device> {
+        Device::from_vid_pid(descriptor.vendor_id(), descriptor.product_id())
+    }
+}
+
+#[cfg(feature = "nusb")]
+impl Vendor {
+    /// Returns the [`Vendor`] for a [`nusb::DeviceInfo`]'s vendor ID, or `None` if no
+    /// such vendor exists in the DB.
+    ///
+    /// ```no_run
+    /// use nusb::MaybeFuture;
+    /// use usb_ids::Vendor;
+    ///
+    /// for info in nusb::list_devices().wait().unwrap() {
+    ///     if let Some(vendor) = Vendor::from_nusb(&info) {
+    ///         println!("vendor: {}", vendor.name());
+    ///     }
+    /// }
+    /// ```
+    pub fn from_nusb(info: &nusb::DeviceInfo) -> Option<&'static Vendor> {
+        Vendor::from_id(info.vendor_id())
+    }
+}
+
+#[cfg(feature = "nusb")]
+impl Device {
+    /// Returns the [`Device`] for a [`nusb::DeviceInfo`]'s vendor and product IDs, or
+    /// `None` if no such device exists in the DB.
+    ///
+    /// ```no_run
+    /// use nusb::MaybeFuture;
+    /// use usb_ids::Device;
+    ///
+    /// for info in nusb::list_devices().wait().unwrap() {
+    ///     if let Some(device) = Device::from_nusb(&info) {
+    ///         println!("device: {}", device.name());
+    ///     }
+    /// }
+    /// ```
+    pub fn from_nusb(info: &nusb::DeviceInfo) -> Option<&'static Device> {
+        Device::from_vid_pid(info.vendor_id(), info.product_id())
+    }
+}
+
+#[cfg(feature = "nusb")]
+impl Class {
+    /// Returns the [`Class`] for a [`nusb::DeviceInfo`]'s device class
+    /// (`bDeviceClass`), or `None` if no such class exists in the DB.
+    ///
+    /// ```no_run
+    /// use nusb::MaybeFuture;
+    /// use usb_ids::Class;
+    ///
+    /// for info in nusb::list_devices().wait().unwrap() {
+    ///     if let Some(class) = Class::from_nusb(&info) {
+    ///         println!("class: {}", class.name());
+    ///     }
+    /// }
+    /// ```
+    pub fn from_nusb(info: &nusb::DeviceInfo) -> Option<&'static Class> {
+        Class::from_id(info.class())
+    }
+}
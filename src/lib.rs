@@ -37,15 +37,141 @@
 //!
 //! See the individual documentation for each structure for more details.
 //!
+//! # `no_std`
+//!
+//! The crate is `no_std` (with `alloc`) when the default `std` feature is disabled.
+//! All of the lookups work the same way without `std`.
+//!
+//! # Trimming the database
+//!
+//! Interface data is embedded by the default `interfaces` feature. Disabling it
+//! drops the interfaces from the generated tables, and [`Device::interfaces`]
+//! then returns an empty iterator.
+//!
+//! Likewise, the class, subclass and protocol tables are embedded by the default
+//! `classes` feature. Without it, [`Classes::iter`] is empty and every class
+//! lookup returns `None`. Disabling both leaves just the vendor and device tables.
+//!
+//! # Binary size
+//!
+//! Names are embedded as ordinary string literals, and the compiler stores each
+//! distinct literal once, so names repeated across entries (e.g. `Keyboard`) don't
+//! add to the binary. Tables that a program never reaches, such as the classes when
+//! only devices are looked up, are dropped by the linker. Most of the size is the
+//! vendor and device names themselves.
+//!
+//! # Device lookup strategy
+//!
+//! By default, [`Device::from_vid_pid`] and [`Device::exists`] find the vendor and
+//! then binary search its devices. The optional `device-map` feature embeds an extra
+//! hash map keyed on the packed vendor and product IDs, making each lookup a single
+//! hash probe. With the bundled database, this adds roughly 170 KiB to a release
+//! binary, so it is left off by default.
+//!
+//! # `serde`
+//!
+//! The optional `serde` feature implements `Serialize` for the database types.
+//! [`Vendor`], [`Device`], [`Interface`], [`Class`], [`SubClass`] and [`Protocol`]
+//! also implement `Deserialize`: only their IDs are read back, and the entity is
+//! resolved against the embedded database, failing if the IDs are unknown.
+//!
+//! # Interoperability
+//!
+//! The optional `rusb` feature adds constructors from `rusb`'s descriptor types,
+//! such as `Device::from_device_descriptor`. Likewise, the optional `nusb` feature
+//! adds constructors from `nusb::DeviceInfo`, such as `Device::from_nusb`.
+//!
+//! # Fuzzy matching
+//!
+//! The optional `fuzzy` feature adds `Vendors::closest`, which ranks vendors by
+//! how closely their names match a (possibly misspelled) query.
+//!
+//! # Custom entries
+//!
+//! With the default `std` feature, `Overlay` adds or renames vendors and devices at
+//! runtime, falling back to the embedded database for everything else.
+//!
+//! # Using another `usb.ids`
+//!
+//! The database is read from the bundled `src/usb.ids` at build time. To embed a
+//! different copy instead (such as a distribution's `/usr/share/hwdata/usb.ids`),
+//! set the `USB_IDS_PATH` environment variable to its path when building.
+//!
+//! To pin a build to a particular snapshot of the database kept in the source tree,
+//! add it as `src/usb_ids/<name>.ids` and set `USB_IDS_SNAPSHOT=<name>`, e.g.
+//! `USB_IDS_SNAPSHOT=2024.12.04` for the snapshot matching the bundled database. An
+//! unknown name fails the build with a list of the available snapshots.
+//! `USB_IDS_PATH` takes precedence when both are set.
+//! The API is the same whichever database is embedded; see [`DB_VERSION`] for the
+//! version that was.
+//!
+//! # Raw tables
+//!
+//! The optional `unstable` feature exposes the generated lookup tables in `raw`.
+//! Their layout may change in any release.
+//!
+//! # Runtime loading
+//!
+//! The optional `parse` feature adds `Database`, which loads a `usb.ids` file at
+//! runtime for users who need fresher data than the bundled copy. To scan a file
+//! without building a `Database`, `database::parse_stream` passes each entry to a
+//! callback as it is read.
+//!
+//! The optional `online` feature (which implies `parse`) adds
+//! `Database::fetch_latest`, which downloads and parses the latest `usb.ids` from
+//! the USB ID Repository. The embedded database is unaffected either way.
+//!
 
 #![warn(missing_docs)]
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+use core::cmp::Ordering;
+use core::convert::TryFrom;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::iter;
+use core::ops::{Bound, RangeBounds};
+use core::str::FromStr;
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+#[cfg(feature = "parse")]
+pub mod database;
+#[cfg(feature = "serde")]
+mod de;
+mod interop;
+pub mod lookup;
+#[cfg(feature = "std")]
+pub mod overlay;
+// The line parsers, shared with the build script
+#[cfg(any(feature = "parse", test))]
+#[allow(dead_code)]
+mod parser;
+pub mod prelude;
+#[cfg(feature = "unstable")]
+pub mod raw;
+
+#[cfg(feature = "online")]
+pub use database::FetchError;
+#[cfg(feature = "parse")]
+pub use database::{Database, ParseError};
+#[cfg(feature = "std")]
+pub use overlay::Overlay;
 
 include!(concat!(env!("OUT_DIR"), "/usb_ids.cg.rs"));
 
 /// Represents a generic USB ID in the USB database.
 ///
 /// Not designed to be used directly; use one of the type aliases instead.
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+///
+/// Ordered and hashed by ID and then by name, since the IDs of nested entries
+/// (like a [`HidUsage`]) are only unique within their parent.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct UsbId<const ID: u8, T> {
     id: T,
     name: &'static str,
@@ -67,6 +193,7 @@ impl<const ID: u8, T: Copy> UsbId<ID, T> {
 ///
 /// Not designed to be used directly; use one of the type aliases instead.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct UsbIdWithChildren<T: Copy, C: 'static> {
     id: T,
     name: &'static str,
@@ -94,17 +221,310 @@ impl<T: Copy, C: 'static> UsbIdWithChildren<T, C> {
 pub struct Vendors;
 impl Vendors {
     /// Returns an iterator over all vendors in the USB database.
-    pub fn iter() -> impl Iterator<Item = &'static Vendor> {
-        USB_IDS.values()
+    ///
+    /// The vendors are yielded in ascending ID order, the same as
+    /// [`Vendors::iter_sorted_by_id`]. This order is guaranteed, so it is safe to
+    /// snapshot.
+    pub fn iter() -> impl DoubleEndedIterator<Item = &'static Vendor> + ExactSizeIterator {
+        USB_VENDORS.iter()
+    }
+
+    /// Returns an iterator over all vendors in the USB database, in ascending ID order.
+    ///
+    /// The iterator can also be reversed, e.g. to list the most recently assigned
+    /// (highest) IDs first.
+    ///
+    /// ```
+    /// use usb_ids::Vendors;
+    /// let ids: Vec<u16> = Vendors::iter_sorted_by_id().map(|v| v.id()).collect();
+    /// assert!(ids.windows(2).all(|w| w[0] < w[1]));
+    ///
+    /// let newest = Vendors::iter_sorted_by_id().rev().next().unwrap();
+    /// assert_eq!(newest.id(), *ids.last().unwrap());
+    /// ```
+    pub fn iter_sorted_by_id(
+    ) -> impl DoubleEndedIterator<Item = &'static Vendor> + ExactSizeIterator {
+        USB_VENDORS.iter()
+    }
+
+    /// Returns an iterator over the vendors whose IDs are in `range`, in ascending
+    /// ID order.
+    ///
+    /// The range is found by bisection, so this is cheap for small ranges, e.g. when
+    /// partitioning the vendors into shards.
+    ///
+    /// ```
+    /// use usb_ids::Vendors;
+    /// let shard: Vec<u16> = Vendors::range(0x1d00..0x1e00).map(|v| v.id()).collect();
+    /// assert!(shard.contains(&0x1d6b));
+    /// assert!(shard.iter().all(|&id| (0x1d00..0x1e00).contains(&id)));
+    ///
+    /// let top = Vendors::range(0x8000..=0xffff).count();
+    /// assert_eq!(Vendors::range(..0x8000).count() + top, Vendors::len());
+    /// ```
+    pub fn range(
+        range: impl RangeBounds<u16>,
+    ) -> impl DoubleEndedIterator<Item = &'static Vendor> + ExactSizeIterator {
+        let start = match range.start_bound() {
+            Bound::Included(&id) => u32::from(id),
+            Bound::Excluded(&id) => u32::from(id) + 1,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&id) => u32::from(id) + 1,
+            Bound::Excluded(&id) => u32::from(id),
+            Bound::Unbounded => u32::from(u16::MAX) + 1,
+        };
+        let lo = USB_VENDORS.partition_point(|v| u32::from(v.id) < start);
+        let hi = USB_VENDORS
+            .partition_point(|v| u32::from(v.id) < end)
+            .max(lo);
+
+        USB_VENDORS[lo..hi].iter()
+    }
+
+    /// Returns an iterator over the vendors whose IDs start with the given hex nibbles,
+    /// in ascending ID order, e.g. for completing a partially typed vendor ID.
+    ///
+    /// `prefix` holds the leading `nibbles` nibbles (at most 4) of the ID, so `0x1d` with
+    /// 2 nibbles matches `0x1d00` to `0x1dff`. A prefix wider than `nibbles` matches
+    /// nothing.
+    ///
+    /// ```
+    /// use usb_ids::Vendors;
+    /// assert!(Vendors::with_id_prefix(0x1d, 2).all(|v| v.id() >> 8 == 0x1d));
+    /// assert!(Vendors::with_id_prefix(0x1d6, 3).any(|v| v.id() == 0x1d6b));
+    /// assert_eq!(Vendors::with_id_prefix(0x1d6b, 4).count(), 1);
+    /// ```
+    pub fn with_id_prefix(
+        prefix: u16,
+        nibbles: u8,
+    ) -> impl DoubleEndedIterator<Item = &'static Vendor> + ExactSizeIterator {
+        let (start, end) = id_prefix_range(prefix, nibbles);
+        let lo = USB_VENDORS.partition_point(|v| u32::from(v.id) < start);
+        let hi = USB_VENDORS.partition_point(|v| u32::from(v.id) < end);
+
+        USB_VENDORS[lo..hi].iter()
+    }
+
+    /// Returns an iterator over all vendors in the USB database, sorted by name
+    /// (ignoring case) and then by ID.
+    ///
+    /// The order is computed when the crate is built, so this is as cheap as [`Vendors::iter`].
+    ///
+    /// ```
+    /// use usb_ids::Vendors;
+    /// for vendor in Vendors::iter_sorted_by_name().take(10) {
+    ///     println!("{}", vendor.name());
+    /// }
+    /// ```
+    pub fn iter_sorted_by_name(
+    ) -> impl DoubleEndedIterator<Item = &'static Vendor> + ExactSizeIterator {
+        USB_VENDORS_BY_NAME
+            .iter()
+            .map(|&i| &USB_VENDORS[i as usize])
+    }
+
+    /// Returns an iterator over all vendors as (vendor ID, name) pairs, in ascending
+    /// ID order, e.g. for populating a flat list.
+    ///
+    /// ```
+    /// use usb_ids::Vendors;
+    /// let pairs: Vec<(u16, &str)> = Vendors::id_name_pairs().collect();
+    /// assert!(pairs.contains(&(0x1d6b, "Linux Foundation")));
+    /// assert_eq!(pairs.len(), Vendors::len());
+    /// ```
+    pub fn id_name_pairs(
+    ) -> impl DoubleEndedIterator<Item = (u16, &'static str)> + ExactSizeIterator {
+        USB_VENDORS.iter().map(|v| (v.id, v.name))
+    }
+
+    /// Returns the number of vendors in the USB database, which is also available
+    /// in `const` contexts as [`VENDOR_COUNT`].
+    ///
+    /// See [`DEVICE_COUNT`] for the total number of devices.
+    pub fn len() -> usize {
+        VENDOR_COUNT
+    }
+
+    /// Returns an iterator over all vendors whose name contains `query`,
+    /// ignoring case, in ascending ID order.
+    ///
+    /// ```
+    /// use usb_ids::Vendors;
+    /// let mut vendors = Vendors::search("linux found");
+    /// assert_eq!(vendors.next().unwrap().id(), 0x1d6b);
+    /// ```
+    pub fn search(query: &str) -> impl Iterator<Item = &'static Vendor> {
+        let query = query.to_lowercase();
+
+        USB_VENDORS
+            .iter()
+            .filter(move |v| contains_ignore_case(v.name, &query))
+    }
+
+    /// Returns up to `n` vendors whose names are closest to `name`, best match first.
+    ///
+    /// Vendors are ranked by the Levenshtein distance between their name and `name`,
+    /// ignoring case, with ties broken by ascending ID. This is useful for "did you mean"
+    /// suggestions when [`Vendor::from_name`] finds nothing.
+    ///
+    /// Requires the `fuzzy` feature.
+    ///
+    /// ```
+    /// use usb_ids::Vendors;
+    /// let suggestions = Vendors::closest("Linux Foundaton", 3);
+    /// assert_eq!(suggestions[0].name(), "Linux Foundation");
+    /// ```
+    #[cfg(feature = "fuzzy")]
+    pub fn closest(name: &str, n: usize) -> Vec<&'static Vendor> {
+        let name = name.to_lowercase();
+        let mut ranked: Vec<(usize, &'static Vendor)> = Vendors::iter()
+            .map(|v| (strsim::levenshtein(&name, &v.name.to_lowercase()), v))
+            .collect();
+        ranked.sort_by_key(|&(distance, v)| (distance, v.id));
+
+        ranked.into_iter().take(n).map(|(_, v)| v).collect()
+    }
+}
+
+/// Returns the `start..end` range of 16-bit IDs whose leading `nibbles` hex nibbles
+/// (at most 4) are `prefix`, widened to `u32` so that `end` can be `0x10000`.
+fn id_prefix_range(prefix: u16, nibbles: u8) -> (u32, u32) {
+    let shift = 4 * (4 - u32::from(nibbles.min(4)));
+    (u32::from(prefix) << shift, (u32::from(prefix) + 1) << shift)
+}
+
+/// An abstraction for iterating over all devices, across all vendors, in the USB database.
+///
+/// ```
+/// use usb_ids::Devices;
+///
+/// for (vendor, device) in Devices::iter() {
+///     println!("vendor: {}, device: {}", vendor.name(), device.name());
+/// }
+/// ```
+pub struct Devices;
+impl Devices {
+    /// Returns an iterator over all devices in the USB database, paired with their [`Vendor`].
+    pub fn iter() -> impl Iterator<Item = (&'static Vendor, &'static Device)> {
+        Vendors::iter().flat_map(|v| v.devices().map(move |d| (v, d)))
+    }
+
+    /// Returns an iterator over only the devices with at least one known
+    /// [`Interface`], paired with their [`Vendor`].
+    ///
+    /// **NOTE**: The USB database includes interface information for very few
+    /// devices, and none when the default `interfaces` feature is disabled.
+    ///
+    /// ```
+    /// use usb_ids::Devices;
+    /// for (vendor, device) in Devices::with_interfaces() {
+    ///     assert!(device.interfaces().next().is_some());
+    ///     println!("{} {} has interfaces", vendor.name(), device.name());
+    /// }
+    /// ```
+    pub fn with_interfaces() -> impl Iterator<Item = (&'static Vendor, &'static Device)> {
+        Self::retain_with_interfaces(Devices::iter())
+    }
+
+    fn retain_with_interfaces(
+        devices: impl Iterator<Item = (&'static Vendor, &'static Device)>,
+    ) -> impl Iterator<Item = (&'static Vendor, &'static Device)> {
+        devices.filter(|(_, d)| d.has_interface_data())
+    }
+}
+
+/// An abstraction for iterating over all interfaces, across all devices, in the USB database.
+///
+/// **NOTE**: The USB database does not include interface information for
+/// most devices.
+pub struct Interfaces;
+impl Interfaces {
+    /// Returns an iterator over all interfaces in the USB database, paired with
+    /// their [`Vendor`] and [`Device`].
+    pub fn iter() -> impl Iterator<Item = (&'static Vendor, &'static Device, &'static Interface)> {
+        Devices::iter().flat_map(|(v, d)| d.interfaces().map(move |i| (v, d, i)))
     }
 }
 
 /// An abstraction for iterating over all classes in the USB database.
 pub struct Classes;
 impl Classes {
-    /// Returns an iterator over all classes in the USB database.
+    /// Returns an iterator over all classes in the USB database, in ascending ID order.
     pub fn iter() -> impl Iterator<Item = &'static Class> {
-        USB_CLASSES.values()
+        CLASS_IDS.iter().filter_map(|&id| Class::from_id(id))
+    }
+
+    /// Returns the number of classes in the USB database.
+    pub fn len() -> usize {
+        USB_CLASSES.len()
+    }
+
+    /// Returns an iterator over the whole class hierarchy in `usb.ids` tree order:
+    /// each class, followed by each of its subclasses, each followed by its protocols.
+    ///
+    /// Classes, subclasses and protocols are each in ascending ID order. A row with
+    /// no subclass is a class, and one with no protocol is a subclass.
+    ///
+    /// ```
+    /// # #[cfg(feature = "classes")] {
+    /// use usb_ids::Classes;
+    /// let rows: Vec<_> = Classes::flatten()
+    ///     .filter(|(class, _, _)| class.id() == 0x03)
+    ///     .map(|(_, subclass, protocol)| (subclass.map(|s| s.id()), protocol.map(|p| p.id())))
+    ///     .collect();
+    /// assert_eq!(rows[..3], [(None, None), (Some(0x00), None), (Some(0x00), Some(0x00))]);
+    /// # }
+    /// ```
+    pub fn flatten() -> impl Iterator<
+        Item = (
+            &'static Class,
+            Option<&'static SubClass>,
+            Option<&'static Protocol>,
+        ),
+    > {
+        Classes::iter().flat_map(|c| {
+            iter::once((c, None, None)).chain(c.sub_classes().flat_map(move |s| {
+                iter::once((c, Some(s), None))
+                    .chain(s.protocols().map(move |p| (c, Some(s), Some(p))))
+            }))
+        })
+    }
+}
+
+/// An abstraction for iterating over all subclasses, across all classes, in the USB database.
+///
+/// ```
+/// use usb_ids::SubClasses;
+///
+/// for (class, subclass) in SubClasses::iter() {
+///     println!("class: {}, subclass: {}", class.name(), subclass.name());
+/// }
+/// ```
+pub struct SubClasses;
+impl SubClasses {
+    /// Returns an iterator over all subclasses in the USB database, paired with their [`Class`].
+    pub fn iter() -> impl Iterator<Item = (&'static Class, &'static SubClass)> {
+        Classes::iter().flat_map(|c| c.sub_classes().map(move |s| (c, s)))
+    }
+}
+
+/// An abstraction for iterating over all protocols, across all subclasses, in the USB database.
+///
+/// ```
+/// use usb_ids::Protocols;
+///
+/// for (class, subclass, protocol) in Protocols::iter() {
+///     println!("{} / {} / {}", class.name(), subclass.name(), protocol.name());
+/// }
+/// ```
+pub struct Protocols;
+impl Protocols {
+    /// Returns an iterator over all protocols in the USB database, paired with
+    /// their [`Class`] and [`SubClass`].
+    pub fn iter() -> impl Iterator<Item = (&'static Class, &'static SubClass, &'static Protocol)> {
+        SubClasses::iter().flat_map(|(c, s)| s.protocols().map(move |p| (c, s, p)))
     }
 }
 
@@ -147,11 +567,141 @@ impl HidUsagePages {
     }
 }
 
+/// An abstraction for iterating over all audio terminal types in the USB database.
+///
+/// ```
+/// use usb_ids::AudioTerminals;
+///
+/// for audio_terminal in AudioTerminals::iter() {
+///     println!("audio terminal: {}", audio_terminal.name());
+/// }
+/// ```
+pub struct AudioTerminals;
+impl AudioTerminals {
+    /// Returns an iterator over all audio terminal types in the USB database.
+    pub fn iter() -> impl Iterator<Item = &'static AudioTerminal> {
+        USB_AUDIO_TERMINALS.values()
+    }
+}
+
+/// An abstraction for iterating over all video terminal types in the USB database.
+///
+/// ```
+/// use usb_ids::VideoTerminals;
+///
+/// for video_terminal in VideoTerminals::iter() {
+///     println!("video terminal: {}", video_terminal.name());
+/// }
+/// ```
+pub struct VideoTerminals;
+impl VideoTerminals {
+    /// Returns an iterator over all video terminal types in the USB database.
+    pub fn iter() -> impl Iterator<Item = &'static VideoTerminal> {
+        USB_VIDEO_TERMINALS.values()
+    }
+}
+
+/// An abstraction for iterating over all HID descriptor types in the USB database.
+///
+/// ```
+/// use usb_ids::Hids;
+///
+/// for hid in Hids::iter() {
+///     println!("hid descriptor: {}", hid.name());
+/// }
+/// ```
+pub struct Hids;
+impl Hids {
+    /// Returns an iterator over all HID descriptor types in the USB database.
+    pub fn iter() -> impl Iterator<Item = &'static Hid> {
+        USB_HID_IDS.values()
+    }
+}
+
+/// An abstraction for iterating over all HID descriptor item types in the USB database.
+///
+/// ```
+/// use usb_ids::HidItemTypes;
+///
+/// for hid_item_type in HidItemTypes::iter() {
+///     println!("hid item type: {}", hid_item_type.name());
+/// }
+/// ```
+pub struct HidItemTypes;
+impl HidItemTypes {
+    /// Returns an iterator over all HID descriptor item types in the USB database.
+    pub fn iter() -> impl Iterator<Item = &'static HidItemType> {
+        USB_HID_R_TYPES.values()
+    }
+}
+
+/// An abstraction for iterating over all physical descriptor bias types in the USB database.
+///
+/// ```
+/// use usb_ids::Biases;
+///
+/// for bias in Biases::iter() {
+///     println!("bias: {}", bias.name());
+/// }
+/// ```
+pub struct Biases;
+impl Biases {
+    /// Returns an iterator over all physical descriptor bias types in the USB database.
+    pub fn iter() -> impl Iterator<Item = &'static Bias> {
+        USB_BIASES.values()
+    }
+}
+
+/// An abstraction for iterating over all physical descriptor item types in the USB database.
+///
+/// ```
+/// use usb_ids::Phys;
+///
+/// for phy in Phys::iter() {
+///     println!("phy: {}", phy.name());
+/// }
+/// ```
+pub struct Phys;
+impl Phys {
+    /// Returns an iterator over all physical descriptor item types in the USB database.
+    pub fn iter() -> impl Iterator<Item = &'static Phy> {
+        USB_PHYS.values()
+    }
+}
+
+/// An abstraction for iterating over all HID descriptor country codes in the USB database.
+///
+/// ```
+/// use usb_ids::HidCountryCodes;
+///
+/// for hid_country_code in HidCountryCodes::iter() {
+///     println!("country code: {}", hid_country_code.name());
+/// }
+/// ```
+pub struct HidCountryCodes;
+impl HidCountryCodes {
+    /// Returns an iterator over all HID descriptor country codes in the USB database.
+    pub fn iter() -> impl Iterator<Item = &'static HidCountryCode> {
+        USB_HID_CCS.values()
+    }
+}
+
+/// Returns whether `haystack` contains the already lowercased `needle`,
+/// lowercasing `haystack` on the fly rather than allocating a copy.
+fn contains_ignore_case(haystack: &str, needle: &str) -> bool {
+    haystack.char_indices().any(|(i, _)| {
+        let mut rest = haystack[i..].chars().flat_map(char::to_lowercase);
+        needle.chars().all(|n| rest.next() == Some(n))
+    })
+}
+
 /// Represents a USB device vendor in the USB database.
 ///
 /// Every device vendor has a vendor ID, a pretty name, and a
 /// list of associated [`Device`]s.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[non_exhaustive]
 pub struct Vendor {
     id: u16,
     name: &'static str,
@@ -159,102 +709,704 @@ pub struct Vendor {
 }
 
 impl Vendor {
-    /// Returns the vendor's ID.
-    pub fn id(&self) -> u16 {
-        self.id
-    }
+    /// Returns the [`Vendor`] with the given name, or `None` if no such vendor
+    /// exists in the DB.
+    ///
+    /// The match is exact but case-insensitive. When several vendors share the
+    /// same name, the vendor with the lowest ID is returned.
+    ///
+    /// ```
+    /// use usb_ids::Vendor;
+    /// let vendor = Vendor::from_name("linux foundation").unwrap();
+    /// assert_eq!(vendor.id(), 0x1d6b);
+    /// ```
+    pub fn from_name(name: &str) -> Option<&'static Vendor> {
+        let id = USB_VENDOR_NAMES.get(name.to_lowercase().as_str())?;
 
-    /// Returns the vendor's name.
-    pub fn name(&self) -> &'static str {
-        self.name
+        Vendor::from_id(*id)
     }
 
-    /// Returns an iterator over the vendor's [`Device`]s.
-    pub fn devices(&self) -> impl Iterator<Item = &'static Device> {
-        self.devices.iter()
+    /// Returns an iterator over every [`Vendor`] with the given name, in ascending ID order.
+    ///
+    /// The match is exact but case-insensitive, as with [`Vendor::from_name`], which
+    /// only returns the first of these.
+    ///
+    /// ```
+    /// use usb_ids::Vendor;
+    /// let ids: Vec<u16> = Vendor::all_from_name("tp-link").map(|v| v.id()).collect();
+    /// assert_eq!(ids[0], 0x0200);
+    /// assert!(ids.len() > 1);
+    /// ```
+    pub fn all_from_name(name: &str) -> impl Iterator<Item = &'static Vendor> {
+        let name = name.to_lowercase();
+        // The name index is sorted by lowercased name and then ID
+        let start = USB_VENDORS_BY_NAME
+            .partition_point(|&i| USB_VENDORS[i as usize].name.to_lowercase() < name);
+
+        USB_VENDORS_BY_NAME[start..]
+            .iter()
+            .map(|&i| &USB_VENDORS[i as usize])
+            .take_while(move |v| v.name.to_lowercase() == name)
     }
-}
-
-/// Represents a single device in the USB database.
-///
-/// Every device has a corresponding vendor, a device ID, a pretty name,
-/// and a list of associated [`Interface`]s.
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-pub struct Device {
-    vendor_id: u16,
-    id: u16,
-    name: &'static str,
-    interfaces: &'static [Interface],
-}
 
-impl Device {
-    /// Returns the [`Device`] corresponding to the given vendor and product IDs,
-    /// or `None` if no such device exists in the DB.
+    /// Returns the [`Vendor`] corresponding to the given ID, or `None` if no such
+    /// vendor exists in the DB.
+    ///
+    /// Unlike [`FromId::from_id`], this is a `const fn` (a binary search over the
+    /// vendors in ID order), so it can be used to resolve vendors at compile time.
     ///
     /// ```
-    /// use usb_ids::Device;
-    /// let device = Device::from_vid_pid(0x1d6b, 0x0003).unwrap();
-    /// assert_eq!(device.name(), "3.0 root hub");
+    /// use usb_ids::Vendor;
+    /// const LINUX_FOUNDATION: Option<&Vendor> = Vendor::from_id_const(0x1d6b);
+    /// assert_eq!(LINUX_FOUNDATION.unwrap().name(), "Linux Foundation");
     /// ```
-    pub fn from_vid_pid(vid: u16, pid: u16) -> Option<&'static Device> {
-        let vendor = Vendor::from_id(vid);
+    pub const fn from_id_const(id: u16) -> Option<&'static Vendor> {
+        let (mut low, mut high) = (0, USB_VENDORS.len());
+        while low < high {
+            let mid = low + (high - low) / 2;
+            let vendor = &USB_VENDORS[mid];
+            if vendor.id == id {
+                return Some(vendor);
+            } else if vendor.id < id {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
 
-        vendor.and_then(|v| v.devices().find(|d| d.id == pid))
+        None
     }
 
-    /// Returns the [`Vendor`] that this device belongs to.
+    /// Returns the [`Vendor`] whose ID is given as little-endian bytes, as found in
+    /// a raw USB device descriptor (`idVendor`), or `None` if no such vendor exists
+    /// in the DB.
     ///
-    /// Looking up a vendor by device is cheap (`O(1)`).
-    pub fn vendor(&self) -> &'static Vendor {
-        USB_IDS.get(&self.vendor_id).unwrap()
+    /// ```
+    /// use usb_ids::Vendor;
+    /// let vendor = Vendor::from_le_bytes([0x6b, 0x1d]).unwrap();
+    /// assert_eq!(vendor.id(), 0x1d6b);
+    /// ```
+    pub fn from_le_bytes(id: [u8; 2]) -> Option<&'static Vendor> {
+        Vendor::from_id(u16::from_le_bytes(id))
     }
 
-    /// Returns a tuple of (vendor id, device/"product" id) for this device.
+    /// Returns whether a vendor with the given ID exists in the DB.
     ///
-    /// This is convenient for interactions with other USB libraries.
-    pub fn as_vid_pid(&self) -> (u16, u16) {
-        (self.vendor_id, self.id)
+    /// ```
+    /// use usb_ids::Vendor;
+    /// assert!(Vendor::exists(0x1d6b));
+    /// ```
+    pub fn exists(id: u16) -> bool {
+        VENDOR_IDS.binary_search(&id).is_ok()
     }
 
-    /// Returns the device's ID.
+    /// Returns the vendor's ID.
     pub fn id(&self) -> u16 {
         self.id
     }
 
-    /// Returns the device's name.
+    /// Returns the vendor's ID as zero-padded lowercase hex, e.g. `"1d6b"`.
+    pub fn id_hex(&self) -> String {
+        format!("{:04x}", self.id)
+    }
+
+    /// Returns the vendor's name.
     pub fn name(&self) -> &'static str {
         self.name
     }
 
-    /// Returns an iterator over the device's [`Interface`]s.
+    /// Returns the vendor's website, or `None` if it isn't known.
     ///
-    /// **NOTE**: The USB database does not include interface information for
-    /// most devices. This list is not authoritative.
-    pub fn interfaces(&self) -> impl Iterator<Item = &'static Interface> {
-        self.interfaces.iter()
+    /// usb.ids has no websites; they come from the crate's `src/vendor_meta.csv`
+    /// sidecar, which only covers some vendors.
+    ///
+    /// ```
+    /// use usb_ids::{FromId, Vendor};
+    /// let vendor = Vendor::from_id(0x1d6b).unwrap();
+    /// assert_eq!(vendor.website(), Some("https://www.linuxfoundation.org/"));
+    /// ```
+    pub fn website(&self) -> Option<&'static str> {
+        USB_VENDOR_WEBSITES.get(&self.id).copied()
     }
-}
 
-/// Represents an interface to a USB device in the USB database.
-///
-/// Every interface has an interface ID (which is an index on the device)
-/// and a pretty name.
-///
-/// **NOTE**: The USB database is not a canonical or authoritative source
-/// of interface information for devices. Users who wish to discover interfaces
-/// on their USB devices should query those devices directly.
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-pub struct Interface {
-    id: u8,
-    name: &'static str,
-}
+    /// Returns an iterator over the vendor's [`Device`]s.
+    pub fn devices(&self) -> impl Iterator<Item = &'static Device> {
+        self.devices.iter()
+    }
+
+    /// Returns the vendor's [`Device`]s as a slice, sorted by ID.
+    ///
+    /// ```
+    /// use usb_ids::{FromId, Vendor};
+    /// let devices = Vendor::from_id(0x1d6b).unwrap().devices_slice();
+    /// assert!(devices.binary_search_by_key(&0x0003, |d| d.id()).is_ok());
+    /// ```
+    pub fn devices_slice(&self) -> &'static [Device] {
+        self.devices
+    }
+
+    /// Returns an iterator over the vendor's devices whose product IDs start with the
+    /// given hex nibbles, in ascending ID order, e.g. for grouping a product family.
+    ///
+    /// As with [`Vendors::with_id_prefix`], `prefix` holds the leading `nibbles`
+    /// nibbles (at most 4) of the ID, so `0x01` with 2 nibbles matches `0x0100` to
+    /// `0x01ff`.
+    ///
+    /// ```
+    /// use usb_ids::{FromId, Vendor};
+    /// let vendor = Vendor::from_id(0x1d6b).unwrap();
+    /// assert!(vendor.devices_with_id_prefix(0x000, 3).all(|d| d.id() >> 4 == 0x000));
+    /// assert_eq!(vendor.devices_with_id_prefix(0x0003, 4).count(), 1);
+    /// ```
+    pub fn devices_with_id_prefix(
+        &self,
+        prefix: u16,
+        nibbles: u8,
+    ) -> impl DoubleEndedIterator<Item = &'static Device> + ExactSizeIterator {
+        let (start, end) = id_prefix_range(prefix, nibbles);
+        let lo = self.devices.partition_point(|d| u32::from(d.id) < start);
+        let hi = self.devices.partition_point(|d| u32::from(d.id) < end);
+
+        self.devices[lo..hi].iter()
+    }
+
+    /// Returns an iterator over the vendor's devices as (product ID, name) pairs, in
+    /// ascending ID order.
+    ///
+    /// ```
+    /// use usb_ids::{FromId, Vendor};
+    /// let vendor = Vendor::from_id(0x1d6b).unwrap();
+    /// for (pid, name) in vendor.device_entries() {
+    ///     println!("{:04x},{}", pid, name);
+    /// }
+    /// assert_eq!(vendor.device_entries().next(), Some((0x0001, "1.1 root hub")));
+    /// ```
+    pub fn device_entries(&self) -> impl Iterator<Item = (u16, &'static str)> {
+        self.devices.iter().map(|d| (d.id, d.name))
+    }
+
+    /// Returns an iterator over the vendor's [`Device`]s, sorted by name (ignoring
+    /// case) and then by ID.
+    ///
+    /// Unlike [`Vendors::iter_sorted_by_name`], the order is computed on each call.
+    ///
+    /// ```
+    /// use usb_ids::{FromId, Vendor};
+    /// let vendor = Vendor::from_id(0x1d6b).unwrap();
+    /// let names: Vec<_> = vendor.devices_sorted_by_name().map(|d| d.name()).collect();
+    /// assert_eq!(names[..2], ["1.1 root hub", "2.0 root hub"]);
+    /// ```
+    pub fn devices_sorted_by_name(&self) -> impl Iterator<Item = &'static Device> {
+        let mut devices: Vec<&'static Device> = self.devices.iter().collect();
+        devices.sort_by_cached_key(|d| (d.name.to_lowercase(), d.id));
+
+        devices.into_iter()
+    }
+
+    /// Returns an iterator over the vendor's [`Device`]s whose name contains
+    /// `query`, ignoring case, in ascending ID order.
+    ///
+    /// ```
+    /// use usb_ids::{FromId, Vendor};
+    /// let vendor = Vendor::from_id(0x1d6b).unwrap();
+    /// assert!(vendor.search_devices("ROOT HUB").all(|d| d.name().contains("root hub")));
+    /// ```
+    pub fn search_devices(&self, query: &str) -> impl Iterator<Item = &'static Device> {
+        let query = query.to_lowercase();
+
+        self.devices
+            .iter()
+            .filter(move |d| contains_ignore_case(d.name, &query))
+    }
+
+    /// Returns the vendor's [`Device`] with the given product ID, or `None` if
+    /// the vendor has no such device in the DB.
+    ///
+    /// ```
+    /// use usb_ids::{FromId, Vendor};
+    /// let vendor = Vendor::from_id(0x1d6b).unwrap();
+    /// let device = vendor.device(0x0003).unwrap();
+    /// assert_eq!(device.name(), "3.0 root hub");
+    /// ```
+    pub fn device(&self, pid: u16) -> Option<&'static Device> {
+        self.devices
+            .binary_search_by_key(&pid, |d| d.id)
+            .ok()
+            .map(|i| &self.devices[i])
+    }
+
+    /// Writes the vendor and its devices and interfaces to `w` in `usb.ids` format:
+    /// one entry per line, with children indented by tabs and IDs separated from
+    /// names by two spaces.
+    ///
+    /// ```
+    /// use usb_ids::{FromId, Vendor};
+    /// let vendor = Vendor::from_id(0x1d6b).unwrap();
+    /// let mut tree = String::new();
+    /// vendor.write_tree(&mut tree).unwrap();
+    /// assert!(tree.starts_with("1d6b  Linux Foundation\n\t0001  1.1 root hub\n"));
+    /// ```
+    pub fn write_tree(&self, w: &mut impl fmt::Write) -> fmt::Result {
+        writeln!(w, "{:04x}  {}", self.id, self.name)?;
+        for device in self.devices {
+            writeln!(w, "\t{:04x}  {}", device.id, device.name)?;
+            for interface in device.interfaces {
+                writeln!(w, "\t\t{:02x}  {}", interface.id, interface.name)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Represents a single device in the USB database.
+///
+/// Every device has a corresponding vendor, a device ID, a pretty name,
+/// and a list of associated [`Interface`]s.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[non_exhaustive]
+pub struct Device {
+    vendor_id: u16,
+    id: u16,
+    name: &'static str,
+    interfaces: &'static [Interface],
+}
+
+impl Device {
+    /// Returns the [`Device`] corresponding to the given vendor and product IDs,
+    /// or `None` if no such device exists in the DB.
+    ///
+    /// This finds the vendor and then binary searches its devices. With the
+    /// `device-map` feature, it is a single hash lookup on the pair instead.
+    ///
+    /// ```
+    /// use usb_ids::Device;
+    /// let device = Device::from_vid_pid(0x1d6b, 0x0003).unwrap();
+    /// assert_eq!(device.name(), "3.0 root hub");
+    /// ```
+    pub fn from_vid_pid(vid: u16, pid: u16) -> Option<&'static Device> {
+        #[cfg(feature = "device-map")]
+        {
+            let &(vendor, device) = USB_DEVICE_IDS.get(&packed_vid_pid(vid, pid))?;
+            Some(&USB_VENDORS[vendor as usize].devices[device as usize])
+        }
+
+        #[cfg(not(feature = "device-map"))]
+        {
+            Vendor::from_id(vid)?.device(pid)
+        }
+    }
+
+    /// Returns the [`Device`] whose vendor and product IDs are given as
+    /// little-endian bytes, as found in a raw USB device descriptor (`idVendor`
+    /// and `idProduct`), or `None` if no such device exists in the DB.
+    ///
+    /// ```
+    /// use usb_ids::Device;
+    /// let device = Device::from_le_bytes([0x6b, 0x1d], [0x03, 0x00]).unwrap();
+    /// assert_eq!(device.as_vid_pid(), (0x1d6b, 0x0003));
+    /// ```
+    pub fn from_le_bytes(vid: [u8; 2], pid: [u8; 2]) -> Option<&'static Device> {
+        Device::from_vid_pid(u16::from_le_bytes(vid), u16::from_le_bytes(pid))
+    }
+
+    /// Resolves each (vendor ID, product ID) pair to its [`Device`], as with
+    /// [`Device::from_vid_pid`], returning the results in the same order as `pairs`.
+    ///
+    /// The pairs are resolved in vendor order, so each vendor is looked up once no
+    /// matter how many of its devices are requested.
+    ///
+    /// ```
+    /// use usb_ids::Device;
+    /// let devices = Device::from_vid_pids(&[(0x1d6b, 0x0003), (0xfffe, 0x0001), (0x1d6b, 0x0002)]);
+    /// assert_eq!(devices[0].unwrap().name(), "3.0 root hub");
+    /// assert!(devices[1].is_none());
+    /// assert_eq!(devices[2].unwrap().name(), "2.0 root hub");
+    /// ```
+    pub fn from_vid_pids(pairs: &[(u16, u16)]) -> Vec<Option<&'static Device>> {
+        let mut order: Vec<usize> = (0..pairs.len()).collect();
+        order.sort_unstable_by_key(|&i| pairs[i]);
+
+        let mut devices = alloc::vec![None; pairs.len()];
+        let mut vendor: Option<(u16, Option<&'static Vendor>)> = None;
+        for i in order {
+            let (vid, pid) = pairs[i];
+            let current = match vendor {
+                Some((id, current)) if id == vid => current,
+                _ => {
+                    let current = Vendor::from_id(vid);
+                    vendor = Some((vid, current));
+                    current
+                }
+            };
+            devices[i] = current.and_then(|v| v.device(pid));
+        }
+
+        devices
+    }
+
+    /// Returns the [`Device`] corresponding to the given vendor and product IDs,
+    /// or a [`LookupError`] saying whether the vendor or just the device is unknown.
+    ///
+    /// ```
+    /// use usb_ids::{Device, LookupError};
+    /// assert!(Device::try_from_vid_pid(0x1d6b, 0x0003).is_ok());
+    /// assert_eq!(
+    ///     Device::try_from_vid_pid(0x1d6b, 0xfffe),
+    ///     Err(LookupError::UnknownDevice { vid: 0x1d6b, pid: 0xfffe })
+    /// );
+    /// assert_eq!(
+    ///     Device::try_from_vid_pid(0xfffe, 0x0001),
+    ///     Err(LookupError::UnknownVendor(0xfffe))
+    /// );
+    /// ```
+    pub fn try_from_vid_pid(vid: u16, pid: u16) -> Result<&'static Device, LookupError> {
+        Vendor::from_id(vid)
+            .ok_or(LookupError::UnknownVendor(vid))?
+            .device(pid)
+            .ok_or(LookupError::UnknownDevice { vid, pid })
+    }
+
+    /// Returns a display name for the given vendor and product IDs, as
+    /// `Vendor Name / Product Name`, substituting `Unknown Vendor (0x1234)` or
+    /// `Unknown Product (0x5678)` for whichever could not be resolved.
+    ///
+    /// ```
+    /// use usb_ids::Device;
+    /// assert_eq!(Device::name_or_unknown(0x1d6b, 0x0003), "Linux Foundation / 3.0 root hub");
+    /// assert_eq!(
+    ///     Device::name_or_unknown(0x1d6b, 0x5678),
+    ///     "Linux Foundation / Unknown Product (0x5678)"
+    /// );
+    /// ```
+    pub fn name_or_unknown(vid: u16, pid: u16) -> String {
+        let vendor = match Vendor::from_id(vid) {
+            Some(vendor) => String::from(vendor.name),
+            None => format!("Unknown Vendor (0x{:04x})", vid),
+        };
+        match Device::from_vid_pid(vid, pid) {
+            Some(device) => format!("{} / {}", vendor, device.name),
+            None => format!("{} / Unknown Product (0x{:04x})", vendor, pid),
+        }
+    }
+
+    /// Returns whether a device with the given vendor and product IDs exists in the DB.
+    ///
+    /// Like [`Device::from_vid_pid`], this is a single hash lookup with the
+    /// `device-map` feature.
+    ///
+    /// ```
+    /// use usb_ids::Device;
+    /// assert!(Device::exists(0x1d6b, 0x0003));
+    /// assert!(!Device::exists(0x1d6b, 0xfffe));
+    /// ```
+    pub fn exists(vid: u16, pid: u16) -> bool {
+        #[cfg(feature = "device-map")]
+        {
+            USB_DEVICE_IDS.contains_key(&packed_vid_pid(vid, pid))
+        }
+
+        #[cfg(not(feature = "device-map"))]
+        {
+            Vendor::from_id(vid)
+                .is_some_and(|v| v.devices.binary_search_by_key(&pid, |d| d.id).is_ok())
+        }
+    }
+
+    /// Returns the [`Vendor`] that this device belongs to.
+    ///
+    /// Looking up a vendor by device is cheap (`O(1)`).
+    pub fn vendor(&self) -> &'static Vendor {
+        Vendor::from_id(self.vendor_id).unwrap()
+    }
+
+    /// Returns a tuple of (vendor id, device/"product" id) for this device.
+    ///
+    /// This is convenient for interactions with other USB libraries.
+    pub fn as_vid_pid(&self) -> (u16, u16) {
+        (self.vendor_id, self.id)
+    }
+
+    /// Returns the device's ID.
+    pub fn id(&self) -> u16 {
+        self.id
+    }
+
+    /// Returns the device's ID as zero-padded lowercase hex, e.g. `"0003"`.
+    pub fn id_hex(&self) -> String {
+        format!("{:04x}", self.id)
+    }
+
+    /// Returns the device's name.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns an iterator over the device's [`Interface`]s.
+    ///
+    /// **NOTE**: The USB database does not include interface information for
+    /// most devices. This list is not authoritative, and is always empty when
+    /// the default `interfaces` feature is disabled.
+    pub fn interfaces(&self) -> impl Iterator<Item = &'static Interface> {
+        self.interfaces.iter()
+    }
+
+    /// Returns whether the DB lists any interfaces for this device.
+    ///
+    /// The USB database only records interfaces when someone has contributed
+    /// them; it has no way to say that a device has no interfaces. A `false`
+    /// result therefore means the interfaces are *unknown*, not that there are
+    /// none. Always `false` when the `interfaces` feature is disabled.
+    ///
+    /// ```
+    /// use usb_ids::Device;
+    /// let device = Device::from_vid_pid(0x1d6b, 0x0003).unwrap();
+    /// assert_eq!(device.has_interface_data(), device.interfaces().count() > 0);
+    /// ```
+    pub fn has_interface_data(&self) -> bool {
+        !self.interfaces.is_empty()
+    }
+
+    /// Returns the device's [`Interface`] with the given ID, or `None` if the
+    /// device has no such interface in the DB.
+    ///
+    /// ```
+    /// use usb_ids::Device;
+    /// let device = Device::from_vid_pid(0x1d6b, 0x0003).unwrap();
+    /// if let Some(interface) = device.interface(0x00) {
+    ///     println!("interface 00: {}", interface.name());
+    /// }
+    /// for interface in device.interfaces() {
+    ///     assert_eq!(device.interface(interface.id()), Some(interface));
+    /// }
+    /// ```
+    pub fn interface(&self, id: u8) -> Option<&'static Interface> {
+        self.interfaces
+            .binary_search_by_key(&id, |i| i.id)
+            .ok()
+            .map(|i| &self.interfaces[i])
+    }
+
+    /// Returns an iterator over the device's interfaces as (interface number, name)
+    /// pairs, in ascending interface number order, e.g. for collecting into a map.
+    ///
+    /// To look up a single interface, use [`Device::interface`].
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use usb_ids::Device;
+    /// let device = Device::from_vid_pid(0x1d6b, 0x0003).unwrap();
+    /// let names: HashMap<u8, &str> = device.interfaces_map().collect();
+    /// assert_eq!(names.len(), device.interfaces().count());
+    /// ```
+    pub fn interfaces_map(&self) -> impl Iterator<Item = (u8, &'static str)> {
+        self.interfaces.iter().map(|i| (i.id, i.name))
+    }
+}
+
+/// Packs a vendor and product ID pair into the key of `USB_DEVICE_IDS`.
+#[cfg(feature = "device-map")]
+fn packed_vid_pid(vid: u16, pid: u16) -> u32 {
+    u32::from(vid) << 16 | u32::from(pid)
+}
+
+/// A vendor and product ID pair, as written by `lsusb` and similar tools (`1d6b:0003`).
+///
+/// Each ID is hex, optionally prefixed with `0x`, and case-insensitive.
+///
+/// ```
+/// use usb_ids::{Device, VidPid};
+///
+/// let vid_pid: VidPid = "0x1D6B:0003".parse().unwrap();
+/// assert_eq!(vid_pid, VidPid { vid: 0x1d6b, pid: 0x0003 });
+///
+/// // or resolve the device directly
+/// let device: &Device = "1d6b:0003".parse().unwrap();
+/// assert_eq!(device.name(), "3.0 root hub");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct VidPid {
+    /// The vendor ID.
+    pub vid: u16,
+    /// The device/"product" ID.
+    pub pid: u16,
+}
+
+impl VidPid {
+    /// Returns the [`Device`] for this pair, or `None` if no such device exists in the DB.
+    pub fn device(&self) -> Option<&'static Device> {
+        Device::from_vid_pid(self.vid, self.pid)
+    }
+}
+
+/// An error returned by [`Device::try_from_vid_pid`] when no such device exists in the DB.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LookupError {
+    /// The vendor ID is not in the DB.
+    UnknownVendor(u16),
+    /// The vendor is in the DB, but the product ID is not one of its devices.
+    UnknownDevice {
+        /// The vendor ID.
+        vid: u16,
+        /// The device/"product" ID.
+        pid: u16,
+    },
+}
+
+impl fmt::Display for LookupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LookupError::UnknownVendor(vid) => write!(f, "unknown vendor {:04x}", vid),
+            LookupError::UnknownDevice { vid, pid } => {
+                write!(f, "unknown device {:04x}:{:04x}", vid, pid)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for LookupError {}
+
+/// An error returned when parsing a [`VidPid`] or a [`Device`] from a `vid:pid` string.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VidPidError {
+    /// The input is not of the form `vid:pid` with hex IDs.
+    Malformed,
+    /// The input is well formed, but no such device exists in the DB.
+    UnknownDevice(VidPid),
+}
+
+impl fmt::Display for VidPidError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VidPidError::Malformed => f.write_str("expected a hex vid:pid pair, e.g. 1d6b:0003"),
+            VidPidError::UnknownDevice(VidPid { vid, pid }) => {
+                write!(f, "unknown device {:04x}:{:04x}", vid, pid)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for VidPidError {}
+
+impl FromStr for VidPid {
+    type Err = VidPidError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        fn hex_id(s: &str) -> Option<u16> {
+            let s = s
+                .strip_prefix("0x")
+                .or_else(|| s.strip_prefix("0X"))
+                .unwrap_or(s);
+            if s.is_empty() || s.len() > 4 || !s.bytes().all(|b| b.is_ascii_hexdigit()) {
+                return None;
+            }
+
+            u16::from_str_radix(s, 16).ok()
+        }
+
+        let (vid, pid) = s.trim().split_once(':').ok_or(VidPidError::Malformed)?;
+        match (hex_id(vid), hex_id(pid)) {
+            (Some(vid), Some(pid)) => Ok(VidPid { vid, pid }),
+            _ => Err(VidPidError::Malformed),
+        }
+    }
+}
+
+impl FromStr for &'static Device {
+    type Err = VidPidError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let vid_pid: VidPid = s.parse()?;
+
+        vid_pid.device().ok_or(VidPidError::UnknownDevice(vid_pid))
+    }
+}
+
+/// Resolves a (vendor ID, product ID) pair, as with [`Device::try_from_vid_pid`].
+///
+/// ```
+/// use std::convert::TryInto;
+/// use usb_ids::{Device, LookupError};
+///
+/// fn name(vid: u16, pid: u16) -> Result<&'static str, LookupError> {
+///     let device: &Device = (vid, pid).try_into()?;
+///     Ok(device.name())
+/// }
+///
+/// assert_eq!(name(0x1d6b, 0x0003), Ok("3.0 root hub"));
+/// assert!(name(0x1d6b, 0xfffe).is_err());
+/// ```
+impl TryFrom<(u16, u16)> for &'static Device {
+    type Error = LookupError;
+
+    fn try_from((vid, pid): (u16, u16)) -> Result<Self, Self::Error> {
+        Device::try_from_vid_pid(vid, pid)
+    }
+}
+
+/// Resolves a vendor ID, as with [`Vendor::from_id`], failing with
+/// [`LookupError::UnknownVendor`].
+///
+/// ```
+/// use std::convert::TryFrom;
+/// use usb_ids::Vendor;
+///
+/// let vendor = <&Vendor>::try_from(0x1d6b).unwrap();
+/// assert_eq!(vendor.name(), "Linux Foundation");
+/// ```
+impl TryFrom<u16> for &'static Vendor {
+    type Error = LookupError;
+
+    fn try_from(id: u16) -> Result<Self, Self::Error> {
+        Vendor::from_id(id).ok_or(LookupError::UnknownVendor(id))
+    }
+}
+
+/// Represents an interface to a USB device in the USB database.
+///
+/// Every interface has an interface ID (which is an index on the device)
+/// and a pretty name. In `usb.ids`, interfaces are the lines indented by two
+/// tabs under a device.
+///
+/// **NOTE**: The USB database is not a canonical or authoritative source
+/// of interface information for devices. Users who wish to discover interfaces
+/// on their USB devices should query those devices directly.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[non_exhaustive]
+pub struct Interface {
+    vendor_id: u16,
+    device_id: u16,
+    id: u8,
+    name: &'static str,
+}
 
 impl Interface {
+    /// Returns the [`Device`] that this interface belongs to.
+    ///
+    /// Looking up a device by interface is cheap (`O(1)` for the vendor, then a
+    /// binary search over its devices).
+    pub fn device(&self) -> &'static Device {
+        Device::from_vid_pid(self.vendor_id, self.device_id).unwrap()
+    }
+
     /// Returns the interface's ID.
+    ///
+    /// This is the interface number on its device (`bInterfaceNumber` in an
+    /// interface descriptor), not an interface class or protocol code; see
+    /// [`Class`] and [`Protocol`] for those.
     pub fn id(&self) -> u8 {
         self.id
     }
 
+    /// Returns the interface's ID as zero-padded lowercase hex, e.g. `"00"`.
+    pub fn id_hex(&self) -> String {
+        format!("{:02x}", self.id)
+    }
+
     /// Returns the interface's name.
     pub fn name(&self) -> &'static str {
         self.name
@@ -266,12 +1418,24 @@ impl Interface {
 /// Every device class has a class ID, a pretty name, and a
 /// list of associated [`SubClass`]s.
 ///
+/// Two classes need some care when printing descriptors:
+///
+/// * The vendor-specific class (`0xff`, see [`Class::is_vendor_specific`]) only lists
+///   the `0xff` subclass and protocol; any other subclass is unknown to the DB. Use
+///   [`SubClass::name_or_vendor_specific`] to name them anyway.
+/// * The miscellaneous class (`0xef`) names its subclasses `?`; the meaningful names
+///   are on their protocols, e.g. `0xef`/`0x02`/`0x01` is "Interface Association".
+///
 /// ```
+/// # #[cfg(feature = "classes")] {
 /// use usb_ids::{Class, Classes, FromId};
 /// let class = Class::from_id(0x03).unwrap();
 /// assert_eq!(class.name(), "Human Interface Device");
+/// # }
 /// ```
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[non_exhaustive]
 pub struct Class {
     id: u8,
     name: &'static str,
@@ -284,15 +1448,116 @@ impl Class {
         self.id
     }
 
+    /// Returns the class's ID as zero-padded lowercase hex, e.g. `"03"`.
+    pub fn id_hex(&self) -> String {
+        format!("{:02x}", self.id)
+    }
+
     /// Returns the class's name.
     pub fn name(&self) -> &'static str {
         self.name
     }
 
+    /// Returns whether this is the vendor-specific class (`0xff`), whose subclasses
+    /// and protocols are defined by each vendor rather than by the USB-IF.
+    ///
+    /// ```
+    /// # #[cfg(feature = "classes")] {
+    /// use usb_ids::{Class, FromId};
+    /// assert!(Class::from_id(0xff).unwrap().is_vendor_specific());
+    /// assert!(!Class::from_id(0x03).unwrap().is_vendor_specific());
+    /// # }
+    /// ```
+    pub fn is_vendor_specific(&self) -> bool {
+        self.id == 0xff
+    }
+
+    /// Returns whether this is class `0x00`, named "(Defined at Interface level)",
+    /// which a device descriptor's `bDeviceClass` uses to defer to the class of
+    /// each interface rather than to name a class of its own.
+    ///
+    /// ```
+    /// # #[cfg(feature = "classes")] {
+    /// use usb_ids::{Class, FromId};
+    /// let class = Class::from_id(0x00).unwrap();
+    /// assert!(class.is_per_interface());
+    /// assert_eq!(class.name(), "(Defined at Interface level)");
+    /// # }
+    /// ```
+    pub fn is_per_interface(&self) -> bool {
+        self.id == 0x00
+    }
+
+    /// Returns whether this is the Human Interface Device class (`0x03`).
+    ///
+    /// ```
+    /// # #[cfg(feature = "classes")] {
+    /// use usb_ids::{Class, FromId};
+    /// assert!(Class::from_id(0x03).unwrap().is_hid());
+    /// # }
+    /// ```
+    pub fn is_hid(&self) -> bool {
+        self.id == 0x03
+    }
+
+    /// Returns whether this is the Mass Storage class (`0x08`).
+    ///
+    /// ```
+    /// # #[cfg(feature = "classes")] {
+    /// use usb_ids::{Class, FromId};
+    /// assert!(Class::from_id(0x08).unwrap().is_mass_storage());
+    /// # }
+    /// ```
+    pub fn is_mass_storage(&self) -> bool {
+        self.id == 0x08
+    }
+
+    /// Returns whether this is the Hub class (`0x09`).
+    ///
+    /// ```
+    /// # #[cfg(feature = "classes")] {
+    /// use usb_ids::{Class, FromId};
+    /// assert!(Class::from_id(0x09).unwrap().is_hub());
+    /// # }
+    /// ```
+    pub fn is_hub(&self) -> bool {
+        self.id == 0x09
+    }
+
     /// Returns an iterator over the class's [`SubClass`]s.
     pub fn sub_classes(&self) -> impl Iterator<Item = &'static SubClass> {
         self.sub_classes.iter()
     }
+
+    /// Returns the class's [`SubClass`]es as a slice, sorted by ID.
+    ///
+    /// ```
+    /// # #[cfg(feature = "classes")] {
+    /// use usb_ids::{Class, FromId};
+    /// let sub_classes = Class::from_id(0x03).unwrap().sub_classes_slice();
+    /// assert!(sub_classes.binary_search_by_key(&0x01, |s| s.id()).is_ok());
+    /// # }
+    /// ```
+    pub fn sub_classes_slice(&self) -> &'static [SubClass] {
+        self.sub_classes
+    }
+
+    /// Returns the class's [`SubClass`] with the given ID, or `None` if the
+    /// class has no such subclass in the DB.
+    ///
+    /// ```
+    /// # #[cfg(feature = "classes")] {
+    /// use usb_ids::{Class, FromId};
+    /// let class = Class::from_id(0x03).unwrap();
+    /// assert_eq!(class.sub_class(0x01).unwrap().name(), "Boot Interface Subclass");
+    /// # }
+    /// ```
+    pub fn sub_class(&self, id: u8) -> Option<&'static SubClass> {
+        self.sub_classes
+            .binary_search_by_key(&id, |s| s.id)
+            .ok()
+            .map(|i| &self.sub_classes[i])
+    }
 }
 
 /// Represents a class subclass in the USB database. Subclasses are part of the
@@ -301,6 +1566,8 @@ impl Class {
 /// Contained within a [`Class`] and may contain a list of associated
 /// [`Protocol`]s.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[non_exhaustive]
 pub struct SubClass {
     class_id: u8,
     id: u8,
@@ -313,16 +1580,16 @@ impl SubClass {
     /// or `None` if no such subclass exists in the DB.
     ///
     /// ```
+    /// # #[cfg(feature = "classes")] {
     /// use usb_ids::SubClass;
     /// let subclass = SubClass::from_cid_scid(0x02, 0x03).unwrap();
     /// assert_eq!(subclass.name(), "Telephone");
     ///
     /// assert!(SubClass::from_cid_scid(0x3c, 0x02).is_none());
+    /// # }
     /// ```
     pub fn from_cid_scid(class_id: u8, id: u8) -> Option<&'static Self> {
-        let class = Class::from_id(class_id);
-
-        class.and_then(|c| c.sub_classes().find(|s| s.id == id))
+        Class::from_id(class_id)?.sub_class(id)
     }
 
     /// Returns the [`Class`] that this subclass belongs to.
@@ -330,10 +1597,12 @@ impl SubClass {
     /// Looking up a class by subclass is cheap (`O(1)`).
     ///
     /// ```
+    /// # #[cfg(feature = "classes")] {
     /// use usb_ids::SubClass;
     /// let subclass = SubClass::from_cid_scid(0x02, 0x03).unwrap();
     /// let class = subclass.class();
     /// assert_eq!(class.id(), 0x02);
+    /// # }
     /// ```
     pub fn class(&self) -> &'static Class {
         USB_CLASSES.get(&self.class_id).unwrap()
@@ -351,56 +1620,381 @@ impl SubClass {
         self.id
     }
 
-    /// Returns the subclass' name.
-    pub fn name(&self) -> &'static str {
-        self.name
+    /// Returns the subclass' ID as zero-padded lowercase hex, e.g. `"01"`.
+    pub fn id_hex(&self) -> String {
+        format!("{:02x}", self.id)
+    }
+
+    /// Returns the subclass' name.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns whether this is the Miscellaneous class' Common Class subclass
+    /// (`0xef/0x02`), which together with protocol `0x01` marks a device whose
+    /// functions are grouped by Interface Association Descriptors (IADs).
+    ///
+    /// The USB-IF defines no other class codes for IADs.
+    ///
+    /// ```
+    /// # #[cfg(feature = "classes")] {
+    /// use usb_ids::SubClass;
+    /// let subclass = SubClass::from_cid_scid(0xef, 0x02).unwrap();
+    /// assert!(subclass.is_iad());
+    /// assert_eq!(subclass.protocol(0x01).unwrap().name(), "Interface Association");
+    /// # }
+    /// ```
+    pub fn is_iad(&self) -> bool {
+        self.as_cid_scid() == (0xef, 0x02)
+    }
+
+    /// Returns an iterator over the subclasses's [`Protocol`]s.
+    ///
+    /// **NOTE**: The USB database nor USB-IF includes protocol information for
+    /// all subclassess. This list is not authoritative.
+    pub fn protocols(&self) -> impl Iterator<Item = &'static Protocol> {
+        self.protocols.iter()
+    }
+
+    /// Returns the subclass' [`Protocol`]s as a slice, sorted by ID.
+    ///
+    /// ```
+    /// # #[cfg(feature = "classes")] {
+    /// use usb_ids::SubClass;
+    /// let protocols = SubClass::from_cid_scid(0x03, 0x01).unwrap().protocols_slice();
+    /// assert!(protocols.binary_search_by_key(&0x01, |p| p.id()).is_ok());
+    /// # }
+    /// ```
+    pub fn protocols_slice(&self) -> &'static [Protocol] {
+        self.protocols
+    }
+
+    /// Returns the subclass' [`Protocol`] with the given ID, or `None` if the
+    /// subclass has no such protocol in the DB.
+    ///
+    /// ```
+    /// # #[cfg(feature = "classes")] {
+    /// use usb_ids::SubClass;
+    /// let subclass = SubClass::from_cid_scid(0x03, 0x01).unwrap();
+    /// assert_eq!(subclass.protocol(0x02).unwrap().name(), "Mouse");
+    /// # }
+    /// ```
+    pub fn protocol(&self, id: u8) -> Option<&'static Protocol> {
+        self.protocols
+            .binary_search_by_key(&id, |p| p.id)
+            .ok()
+            .map(|i| &self.protocols[i])
+    }
+
+    /// Returns the name of the subclass with the given class and subclass IDs.
+    ///
+    /// Unlike [`SubClass::from_cid_scid`], subclasses of the vendor-specific class
+    /// (`0xff`) that are not in the DB are named `"Vendor Specific Subclass"`. Other
+    /// unknown subclasses return `None`.
+    ///
+    /// ```
+    /// # #[cfg(feature = "classes")] {
+    /// use usb_ids::SubClass;
+    /// assert_eq!(SubClass::name_or_vendor_specific(0x03, 0x01), Some("Boot Interface Subclass"));
+    /// assert_eq!(SubClass::name_or_vendor_specific(0xff, 0x42), Some("Vendor Specific Subclass"));
+    /// assert_eq!(SubClass::name_or_vendor_specific(0x03, 0x42), None);
+    /// # }
+    /// ```
+    pub fn name_or_vendor_specific(class_id: u8, id: u8) -> Option<&'static str> {
+        match SubClass::from_cid_scid(class_id, id) {
+            Some(subclass) => Some(subclass.name),
+            None if class_id == 0xff => Some("Vendor Specific Subclass"),
+            None => None,
+        }
+    }
+}
+
+/// These are tags for UsbId type aliases to make them unique and allow a
+/// [`FromId`] for each alias. The values are arbitrary but must be unique.
+///
+/// [`std::marker::PhantomData`] would be nicer but was unable to figure out a
+/// generic way to add the _tag: PhantomData in the ToToken trait
+/// implementation within build.rs
+const AT_TAG: u8 = 1;
+const HID_TAG: u8 = 2;
+const HID_TYPE_TAG: u8 = 3;
+const HID_USAGE_TAG: u8 = 4;
+const BIAS_TAG: u8 = 5;
+const PHY_TAG: u8 = 6;
+const DIALECT_TAG: u8 = 7;
+const HCC_TAG: u8 = 8;
+const VT_TAG: u8 = 9;
+
+/// Represents a subclass protocol in the USB database.
+///
+/// Protocols are part of the USB class code triplet (base class, subclass,
+/// protocol), contained within a [`SubClass`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[non_exhaustive]
+pub struct Protocol {
+    class_id: u8,
+    sub_class_id: u8,
+    id: u8,
+    name: &'static str,
+}
+
+impl Protocol {
+    /// Returns the [`Protocol`] corresponding to the given class, subclass, and protocol IDs,
+    /// or `None` if no such protocol exists in the DB.
+    ///
+    /// ```
+    /// # #[cfg(feature = "classes")] {
+    /// use usb_ids::Protocol;
+    /// let protocol = Protocol::from_cid_scid_pid(0x02, 0x02, 0x05).unwrap();
+    /// assert_eq!(protocol.name(), "AT-commands (3G)");
+    /// # }
+    /// ```
+    pub fn from_cid_scid_pid(class_id: u8, subclass_id: u8, id: u8) -> Option<&'static Self> {
+        SubClass::from_cid_scid(class_id, subclass_id)?.protocol(id)
+    }
+
+    /// Returns the [`SubClass`] that this protocol belongs to.
+    ///
+    /// ```
+    /// # #[cfg(feature = "classes")] {
+    /// use usb_ids::Protocol;
+    /// let protocol = Protocol::from_cid_scid_pid(0x03, 0x01, 0x01).unwrap();
+    /// assert_eq!(protocol.sub_class().name(), "Boot Interface Subclass");
+    /// # }
+    /// ```
+    pub fn sub_class(&self) -> &'static SubClass {
+        SubClass::from_cid_scid(self.class_id, self.sub_class_id).unwrap()
+    }
+
+    /// Returns the [`Class`] that this protocol belongs to.
+    ///
+    /// ```
+    /// # #[cfg(feature = "classes")] {
+    /// use usb_ids::Protocol;
+    /// let protocol = Protocol::from_cid_scid_pid(0x03, 0x01, 0x01).unwrap();
+    /// assert_eq!(protocol.class().name(), "Human Interface Device");
+    /// # }
+    /// ```
+    pub fn class(&self) -> &'static Class {
+        USB_CLASSES.get(&self.class_id).unwrap()
+    }
+
+    /// Returns a tuple of (class id, subclass id, protocol id) for this protocol.
+    ///
+    /// This is convenient for interactions with other USB libraries.
+    ///
+    /// ```
+    /// # #[cfg(feature = "classes")] {
+    /// use usb_ids::Protocol;
+    /// let protocol = Protocol::from_cid_scid_pid(0x03, 0x01, 0x02).unwrap();
+    /// assert_eq!(protocol.as_cid_scid_pid(), (0x03, 0x01, 0x02));
+    /// # }
+    /// ```
+    pub fn as_cid_scid_pid(&self) -> (u8, u8, u8) {
+        (self.class_id, self.sub_class_id, self.id)
+    }
+
+    /// Returns the protocol's ID.
+    pub fn id(&self) -> u8 {
+        self.id
+    }
+
+    /// Returns the protocol's ID as zero-padded lowercase hex, e.g. `"01"`.
+    pub fn id_hex(&self) -> String {
+        format!("{:02x}", self.id)
+    }
+
+    /// Returns the protocol's name.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns the names of the protocol's class, subclass and the protocol itself,
+    /// joined with ` / `.
+    ///
+    /// ```
+    /// # #[cfg(feature = "classes")] {
+    /// use usb_ids::Protocol;
+    /// let protocol = Protocol::from_cid_scid_pid(0x03, 0x01, 0x01).unwrap();
+    /// assert_eq!(
+    ///     protocol.qualified_name(),
+    ///     "Human Interface Device / Boot Interface Subclass / Keyboard"
+    /// );
+    /// # }
+    /// ```
+    pub fn qualified_name(&self) -> String {
+        let sub_class = self.sub_class();
+
+        format!(
+            "{} / {} / {}",
+            sub_class.class().name,
+            sub_class.name,
+            self.name
+        )
+    }
+}
+
+/// Returns the version of the bundled USB database, e.g. `"2024.12.04"`.
+///
+/// This is the same as [`DB_VERSION`].
+///
+/// ```
+/// println!("usb.ids version {}", usb_ids::db_version());
+/// ```
+pub fn db_version() -> &'static str {
+    DB_VERSION
+}
+
+/// Returns the date of the bundled USB database, e.g. `"2024-12-04 20:34:02"`.
+///
+/// This is the same as [`DB_DATE`].
+pub fn db_date() -> &'static str {
+    DB_DATE
+}
+
+/// Writes the embedded database to `w` in `usb.ids` format: the version header,
+/// then the vendors and classes in ascending ID order.
+///
+/// Only the vendor and class sections are written. Parsing the output (for example
+/// with `Database`) yields the same vendors and classes as the embedded database.
+///
+/// ```
+/// let mut out = Vec::new();
+/// usb_ids::write_database(&mut out).unwrap();
+/// let out = String::from_utf8(out).unwrap();
+/// assert!(out.contains("\n1d6b  Linux Foundation\n\t0001  1.1 root hub\n"));
+/// ```
+#[cfg(feature = "std")]
+pub fn write_database(w: &mut impl std::io::Write) -> std::io::Result<()> {
+    writeln!(w, "# Version: {}", DB_VERSION)?;
+    writeln!(w, "# Date:    {}", DB_DATE)?;
+
+    writeln!(w)?;
+    let mut tree = String::new();
+    for vendor in Vendors::iter_sorted_by_id() {
+        tree.clear();
+        vendor
+            .write_tree(&mut tree)
+            .expect("writing to a String cannot fail");
+        w.write_all(tree.as_bytes())?;
+    }
+
+    writeln!(w)?;
+    for row in Classes::flatten() {
+        match row {
+            (class, None, _) => writeln!(w, "C {:02x}  {}", class.id, class.name)?,
+            (_, Some(sub_class), None) => {
+                writeln!(w, "\t{:02x}  {}", sub_class.id, sub_class.name)?
+            }
+            (_, _, Some(protocol)) => writeln!(w, "\t\t{:02x}  {}", protocol.id, protocol.name)?,
+        }
     }
 
-    /// Returns an iterator over the subclasses's [`Protocol`]s.
-    ///
-    /// **NOTE**: The USB database nor USB-IF includes protocol information for
-    /// all subclassess. This list is not authoritative.
-    pub fn protocols(&self) -> impl Iterator<Item = &'static Protocol> {
-        self.protocols.iter()
-    }
+    Ok(())
 }
 
-/// These are tags for UsbId type aliases to make them unique and allow a
-/// [`FromId`] for each alias. The values are arbitrary but must be unique.
+/// Resolves a USB class code triplet (base class, subclass, protocol), as found
+/// in device and interface descriptors, in a single walk of the class hierarchy.
 ///
-/// [`std::marker::PhantomData`] would be nicer but was unable to figure out a
-/// generic way to add the _tag: PhantomData in the ToToken trait
-/// implementation within build.rs
-const PROTOCOL_TAG: u8 = 0;
-const AT_TAG: u8 = 1;
-const HID_TAG: u8 = 2;
-const HID_TYPE_TAG: u8 = 3;
-const HID_USAGE_TAG: u8 = 4;
-const BIAS_TAG: u8 = 5;
-const PHY_TAG: u8 = 6;
-const DIALECT_TAG: u8 = 7;
-const HCC_TAG: u8 = 8;
-const VT_TAG: u8 = 9;
+/// Each level is `None` if it does not exist in the DB, and the subclass and
+/// protocol are always `None` when their parent could not be resolved.
+///
+/// ```
+/// # #[cfg(feature = "classes")] {
+/// use usb_ids::lookup_class;
+/// let (class, subclass, protocol) = lookup_class(0x03, 0x01, 0x02);
+/// assert_eq!(class.unwrap().name(), "Human Interface Device");
+/// assert_eq!(subclass.unwrap().name(), "Boot Interface Subclass");
+/// assert_eq!(protocol.unwrap().name(), "Mouse");
+///
+/// let (class, subclass, protocol) = lookup_class(0x03, 0x7f, 0x02);
+/// assert!(class.is_some());
+/// assert!(subclass.is_none() && protocol.is_none());
+/// # }
+/// ```
+pub fn lookup_class(
+    class_id: u8,
+    subclass_id: u8,
+    protocol_id: u8,
+) -> (
+    Option<&'static Class>,
+    Option<&'static SubClass>,
+    Option<&'static Protocol>,
+) {
+    let class = Class::from_id(class_id);
+    let subclass = class.and_then(|c| c.sub_class(subclass_id));
+    let protocol = subclass.and_then(|s| s.protocol(protocol_id));
+
+    (class, subclass, protocol)
+}
 
-/// Represents a subclass protocol in the USB database.
+/// A USB class code triplet (base class, subclass, protocol), as carried at the
+/// device level by some device descriptors or by an interface descriptor, resolved
+/// as far as the DB allows.
 ///
-/// Protocols are part of the USB class code triplet (base class, subclass,
-/// protocol), contained within a [`SubClass`].
-pub type Protocol = UsbId<PROTOCOL_TAG, u8>;
+/// The raw IDs are always kept; each level is `None` when it (or its parent) could
+/// not be resolved.
+///
+/// ```
+/// # #[cfg(feature = "classes")] {
+/// use usb_ids::ClassPath;
+/// let path = ClassPath::from_triple(0x03, 0x01, 0x02).unwrap();
+/// assert_eq!(path.class.unwrap().name(), "Human Interface Device");
+/// assert_eq!(path.protocol.unwrap().name(), "Mouse");
+/// assert_eq!(
+///     path.to_string(),
+///     "Human Interface Device / Boot Interface Subclass / Mouse"
+/// );
+///
+/// assert!(ClassPath::from_triple(0x42, 0x00, 0x00).is_none());
+///
+/// let path = ClassPath::from_interface_descriptor(0x03, 0x01, 0x7f);
+/// assert_eq!(path.protocol_id, 0x7f);
+/// assert_eq!(
+///     path.to_string(),
+///     "Human Interface Device / Boot Interface Subclass / Unknown (0x7f)"
+/// );
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ClassPath {
+    /// The class ID.
+    pub class_id: u8,
+    /// The subclass ID.
+    pub sub_class_id: u8,
+    /// The protocol ID.
+    pub protocol_id: u8,
+    /// The class, if it exists in the DB.
+    pub class: Option<&'static Class>,
+    /// The subclass, if it and its class exist in the DB.
+    pub sub_class: Option<&'static SubClass>,
+    /// The protocol, if it, its subclass and its class exist in the DB.
+    pub protocol: Option<&'static Protocol>,
+}
 
-impl Protocol {
-    /// Returns the [`Protocol`] corresponding to the given class, subclass, and protocol IDs,
-    /// or `None` if no such protocol exists in the DB.
-    ///
-    /// ```
-    /// use usb_ids::Protocol;
-    /// let protocol = Protocol::from_cid_scid_pid(0x02, 0x02, 0x05).unwrap();
-    /// assert_eq!(protocol.name(), "AT-commands (3G)");
-    /// ```
-    pub fn from_cid_scid_pid(class_id: u8, subclass_id: u8, id: u8) -> Option<&'static Self> {
-        let subclass = SubClass::from_cid_scid(class_id, subclass_id);
+impl ClassPath {
+    /// Returns the [`ClassPath`] for the given class, subclass, and protocol IDs,
+    /// or `None` if the class does not exist in the DB.
+    pub fn from_triple(class_id: u8, subclass_id: u8, protocol_id: u8) -> Option<Self> {
+        let path = Self::from_interface_descriptor(class_id, subclass_id, protocol_id);
+        path.class.map(|_| path)
+    }
 
-        subclass.and_then(|s| s.protocols().find(|p| p.id == id))
+    /// Resolves the class code triplet from an interface descriptor
+    /// (`bInterfaceClass`, `bInterfaceSubClass`, `bInterfaceProtocol`). Unlike
+    /// [`ClassPath::from_triple`], the class doesn't have to exist in the DB.
+    pub fn from_interface_descriptor(class_id: u8, sub_class_id: u8, protocol_id: u8) -> Self {
+        let (class, sub_class, protocol) = lookup_class(class_id, sub_class_id, protocol_id);
+
+        ClassPath {
+            class_id,
+            sub_class_id,
+            protocol_id,
+            class,
+            sub_class,
+            protocol,
+        }
     }
 }
 
@@ -480,6 +2074,28 @@ impl HidUsage {
 
         page.children().find(|u| u.id() == id)
     }
+
+    /// Returns the [`HidUsage`] corresponding to the given 16-bit usage page and usage ID,
+    /// or `None` if no such usage exists in the DB.
+    ///
+    /// This is convenient when decoding extended (32-bit) usages from a HID report
+    /// descriptor, where the usage page is the high word and the usage ID the low word.
+    /// The USB database only lists usage pages up to `0xff`, so vendor-defined pages
+    /// (`0xff00` and above) always return `None`.
+    ///
+    /// ```
+    /// use usb_ids::HidUsage;
+    /// let extended: u32 = 0x0001_0006;
+    /// let hid_usage = HidUsage::from_page_usage((extended >> 16) as u16, extended as u16).unwrap();
+    /// assert_eq!(hid_usage.name(), "Keyboard");
+    ///
+    /// assert!(HidUsage::from_page_usage(0xff00, 0x01).is_none());
+    /// ```
+    pub fn from_page_usage(page_id: u16, id: u16) -> Option<&'static Self> {
+        let page_id = u8::try_from(page_id).ok()?;
+
+        Self::from_pageid_uid(page_id, id)
+    }
 }
 
 /// Represents physical descriptor bias type in the USB database.
@@ -517,6 +2133,25 @@ pub type Phy = UsbId<PHY_TAG, u8>;
 pub type Language = UsbIdWithChildren<u16, Dialect>;
 
 impl Language {
+    /// Returns the [`Language`] for a full `wLANGID` from a USB string descriptor,
+    /// using its primary language ID (the low 10 bits) and ignoring the sub-language.
+    ///
+    /// ```
+    /// use usb_ids::Language;
+    /// let language = Language::from_langid(0x0807).unwrap();
+    /// assert_eq!(language.primary_name(), "German");
+    /// ```
+    pub fn from_langid(langid: u16) -> Option<&'static Self> {
+        Language::from_id(langid & 0x3ff)
+    }
+
+    /// Returns the name of the primary language, e.g. `"German"`, without any dialect.
+    ///
+    /// This is the same as [`Language::name`], as dialects are separate entries.
+    pub fn primary_name(&self) -> &'static str {
+        self.name()
+    }
+
     /// Returns an iterator over the language's [`Dialect`]s.
     pub fn dialects(&self) -> impl Iterator<Item = &'static Dialect> {
         self.children()
@@ -550,6 +2185,31 @@ impl Dialect {
 
         language.children().find(|d| d.id() == id)
     }
+
+    /// Returns the [`Dialect`] corresponding to the given primary language and
+    /// sub-language IDs. This is the same as [`Dialect::from_lid_did`].
+    ///
+    /// ```
+    /// use usb_ids::Dialect;
+    /// let dialect = Dialect::from_lang_dialect(0x0007, 0x02).unwrap();
+    /// assert_eq!(dialect.name(), "Swiss");
+    /// ```
+    pub fn from_lang_dialect(lang: u16, dialect: u8) -> Option<&'static Self> {
+        Self::from_lid_did(lang, dialect)
+    }
+
+    /// Returns the [`Dialect`] for a full `wLANGID` from a USB string descriptor,
+    /// which carries the primary language ID in its low 10 bits and the sub-language
+    /// ID in its high 6 bits.
+    ///
+    /// ```
+    /// use usb_ids::Dialect;
+    /// let dialect = Dialect::from_langid(0x0807).unwrap();
+    /// assert_eq!(dialect.name(), "Swiss");
+    /// ```
+    pub fn from_langid(langid: u16) -> Option<&'static Self> {
+        Self::from_lid_did(langid & 0x3ff, (langid >> 10) as u8)
+    }
 }
 
 /// Represents a HID descriptor country code in the USB database.
@@ -585,7 +2245,7 @@ pub trait FromId<T> {
 
 impl FromId<u16> for Vendor {
     fn from_id(id: u16) -> Option<&'static Self> {
-        USB_IDS.get(&id)
+        USB_IDS.get(&id).map(|&i| &USB_VENDORS[i as usize])
     }
 }
 
@@ -617,48 +2277,496 @@ impl FromId<u8> for HidUsagePage {
     fn from_id(id: u8) -> Option<&'static Self> {
         USB_HUTS.get(&id)
     }
-}
+}
+
+impl FromId<u8> for Bias {
+    fn from_id(id: u8) -> Option<&'static Self> {
+        USB_BIASES.get(&id)
+    }
+}
+
+impl FromId<u8> for Phy {
+    fn from_id(id: u8) -> Option<&'static Self> {
+        USB_PHYS.get(&id)
+    }
+}
+
+impl FromId<u16> for Language {
+    fn from_id(id: u16) -> Option<&'static Self> {
+        USB_LANGS.get(&id)
+    }
+}
+
+impl FromId<u8> for HidCountryCode {
+    fn from_id(id: u8) -> Option<&'static Self> {
+        USB_HID_CCS.get(&id)
+    }
+}
+
+impl FromId<u16> for VideoTerminal {
+    fn from_id(id: u16) -> Option<&'static Self> {
+        USB_VIDEO_TERMINALS.get(&id)
+    }
+}
+
+/// A vendor from either the embedded database or a runtime `Database`, so that code
+/// can work with both.
+///
+/// ```
+/// use usb_ids::{FromId, Vendor, VendorRef};
+///
+/// fn describe(vendor: &impl VendorRef) -> String {
+///     format!("{:04x} {}", vendor.id(), vendor.name())
+/// }
+///
+/// assert_eq!(describe(Vendor::from_id(0x1d6b).unwrap()), "1d6b Linux Foundation");
+/// ```
+pub trait VendorRef {
+    /// Returns the vendor's ID.
+    fn id(&self) -> u16;
+
+    /// Returns the vendor's name.
+    fn name(&self) -> &str;
+}
+
+/// A device from either the embedded database or a runtime `Database`, so that code
+/// can work with both.
+///
+/// ```
+/// use usb_ids::{Device, DeviceRef};
+///
+/// fn describe(device: &impl DeviceRef) -> String {
+///     let (vid, pid) = device.as_vid_pid();
+///     format!("{:04x}:{:04x} {}", vid, pid, device.name())
+/// }
+///
+/// let device = Device::from_vid_pid(0x1d6b, 0x0003).unwrap();
+/// assert_eq!(describe(device), "1d6b:0003 3.0 root hub");
+/// ```
+pub trait DeviceRef {
+    /// Returns a tuple of (vendor id, device/"product" id) for this device.
+    fn as_vid_pid(&self) -> (u16, u16);
+
+    /// Returns the device's name.
+    fn name(&self) -> &str;
+}
+
+impl VendorRef for Vendor {
+    fn id(&self) -> u16 {
+        self.id
+    }
+
+    fn name(&self) -> &str {
+        self.name
+    }
+}
+
+impl DeviceRef for Device {
+    fn as_vid_pid(&self) -> (u16, u16) {
+        (self.vendor_id, self.id)
+    }
+
+    fn name(&self) -> &str {
+        self.name
+    }
+}
+
+/// Formats the vendor in the `usb.ids` form: `1d6b  Linux Foundation`.
+impl fmt::Display for Vendor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04x}  {}", self.id, self.name)
+    }
+}
+
+/// Formats the device in the `usb.ids` form: `0003  3.0 root hub`.
+impl fmt::Display for Device {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04x}  {}", self.id, self.name)
+    }
+}
+
+/// Formats the interface in the `usb.ids` form: `00  Name`.
+impl fmt::Display for Interface {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02x}  {}", self.id, self.name)
+    }
+}
+
+/// Formats the class in the `usb.ids` form: `03  Human Interface Device`.
+impl fmt::Display for Class {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02x}  {}", self.id, self.name)
+    }
+}
+
+/// Formats the subclass in the `usb.ids` form: `01  Boot Interface Subclass`.
+impl fmt::Display for SubClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02x}  {}", self.id, self.name)
+    }
+}
+
+/// Formats the protocol in the `usb.ids` form: `01  Keyboard`.
+impl fmt::Display for Protocol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02x}  {}", self.id, self.name)
+    }
+}
+
+/// Formats every level from the class down, separated by ` / `, with unresolved ones
+/// as `Unknown (0xNN)`: `Human Interface Device / Boot Interface Subclass / Mouse`.
+impl fmt::Display for ClassPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn level(f: &mut fmt::Formatter<'_>, name: Option<&str>, id: u8) -> fmt::Result {
+            match name {
+                Some(name) => f.write_str(name),
+                None => write!(f, "Unknown (0x{:02x})", id),
+            }
+        }
+
+        level(f, self.class.map(|c| c.name), self.class_id)?;
+        f.write_str(" / ")?;
+        level(f, self.sub_class.map(|s| s.name), self.sub_class_id)?;
+        f.write_str(" / ")?;
+        level(f, self.protocol.map(|p| p.name()), self.protocol_id)
+    }
+}
+
+/// Implements `PartialOrd`, `Ord` and `Hash` by a key that uniquely identifies an
+/// entry in the DB, so that they agree with the derived `Eq`.
+macro_rules! impl_cmp_by_key {
+    ($($(#[$meta:meta])* $ty:ty => |$entry:ident| $key:expr;)*) => {$(
+        $(#[$meta])*
+        impl PartialOrd for $ty {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        $(#[$meta])*
+        impl Ord for $ty {
+            fn cmp(&self, other: &Self) -> Ordering {
+                let key = |$entry: &Self| $key;
+                key(self).cmp(&key(other))
+            }
+        }
+
+        $(#[$meta])*
+        impl Hash for $ty {
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                let key = |$entry: &Self| $key;
+                key(self).hash(state)
+            }
+        }
+    )*};
+}
+
+impl_cmp_by_key! {
+    /// Orders and hashes vendors by ID.
+    Vendor => |v| v.id;
+    /// Orders and hashes devices by vendor ID, then device ID.
+    Device => |d| (d.vendor_id, d.id);
+    /// Orders and hashes interfaces by vendor ID, device ID, then interface ID.
+    Interface => |i| (i.vendor_id, i.device_id, i.id);
+    /// Orders and hashes classes by ID.
+    Class => |c| c.id;
+    /// Orders and hashes subclasses by class ID, then subclass ID.
+    SubClass => |s| (s.class_id, s.id);
+    /// Orders and hashes protocols by class ID, subclass ID, then protocol ID.
+    Protocol => |p| (p.class_id, p.sub_class_id, p.id);
+}
+
+/// Orders top-level entries (like a [`HidUsagePage`]) by ID.
+impl<T: Copy + Ord, C: 'static + Eq> PartialOrd for UsbIdWithChildren<T, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Orders top-level entries (like a [`HidUsagePage`]) by ID.
+impl<T: Copy + Ord, C: 'static + Eq> Ord for UsbIdWithChildren<T, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.id.cmp(&other.id)
+    }
+}
+
+/// Hashes top-level entries (like a [`HidUsagePage`]) by ID.
+impl<T: Copy + Hash, C: 'static> Hash for UsbIdWithChildren<T, C> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state)
+    }
+}
+
+/// Implements `AsRef<str>`, and `From<&T>` for `&'static str`, returning the entry's name.
+macro_rules! impl_name_ref {
+    ($($ty:ty),* $(,)?) => {$(
+        impl AsRef<str> for $ty {
+            fn as_ref(&self) -> &str {
+                self.name
+            }
+        }
+
+        impl From<&$ty> for &'static str {
+            fn from(entry: &$ty) -> Self {
+                entry.name
+            }
+        }
+    )*};
+}
+
+impl_name_ref!(Vendor, Device, Interface, Class, SubClass, Protocol);
+
+/// Returns the entry's name, e.g. for a [`Language`].
+impl<T: Copy, C: 'static> AsRef<str> for UsbIdWithChildren<T, C> {
+    fn as_ref(&self) -> &str {
+        self.name
+    }
+}
+
+/// Returns the entry's name, e.g. for a [`Hid`].
+impl<const ID: u8, T> AsRef<str> for UsbId<ID, T> {
+    fn as_ref(&self) -> &str {
+        self.name
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The bundled usb.ids lists no interfaces, so interface handling is tested
+    // against this hand-built device, which reuses the IDs of `1d6b:0003`.
+    static FIXTURE_INTERFACES: &[Interface] = &[
+        Interface {
+            vendor_id: 0x1d6b,
+            device_id: 0x0003,
+            id: 0x00,
+            name: "Hub",
+        },
+        Interface {
+            vendor_id: 0x1d6b,
+            device_id: 0x0003,
+            id: 0x02,
+            name: "Debug",
+        },
+    ];
+    static FIXTURE_DEVICE: Device = Device {
+        vendor_id: 0x1d6b,
+        id: 0x0003,
+        name: "3.0 root hub",
+        interfaces: FIXTURE_INTERFACES,
+    };
+
+    #[test]
+    fn test_from_id() {
+        let vendor = Vendor::from_id(0x1d6b).unwrap();
+
+        assert_eq!(vendor.name(), "Linux Foundation");
+        assert_eq!(vendor.id(), 0x1d6b);
+        assert_eq!(Vendor::from_le_bytes(0x1d6bu16.to_le_bytes()), Some(vendor));
+        assert!(Vendor::from_le_bytes([0x1d, 0x6b]).is_none());
+    }
+
+    #[test]
+    fn test_website() {
+        let vendor = Vendor::from_id(0x1d6b).unwrap();
+        assert_eq!(vendor.website(), Some("https://www.linuxfoundation.org/"));
+
+        // every sidecar entry should refer to a vendor in the DB
+        for id in USB_VENDOR_WEBSITES.keys() {
+            assert!(Vendor::exists(*id), "{:04x}", id);
+        }
+        assert!(Vendors::iter().any(|v| v.website().is_none()));
+    }
+
+    #[test]
+    fn test_vendor_from_name() {
+        let vendor = Vendor::from_name("Linux Foundation").unwrap();
+        assert_eq!(vendor.id(), 0x1d6b);
+
+        let vendor = Vendor::from_name("LINUX FOUNDATION").unwrap();
+        assert_eq!(vendor.id(), 0x1d6b);
+
+        assert!(Vendor::from_name("Linux").is_none());
+
+        // shared names resolve to the lowest ID
+        let vendor = Vendor::from_name("TP-Link").unwrap();
+        assert_eq!(vendor.id(), 0x0200);
+
+        let vendors: Vec<_> = Vendor::all_from_name("TP-LINK").collect();
+        assert_eq!(vendors.first(), Some(&vendor));
+        assert!(vendors.len() > 1);
+        assert!(vendors.windows(2).all(|w| w[0].id() < w[1].id()));
+        assert!(vendors.iter().all(|v| v.name() == "TP-Link"));
+
+        assert_eq!(Vendor::all_from_name("Linux Foundation").count(), 1);
+        assert_eq!(Vendor::all_from_name("Linux").count(), 0);
+    }
+
+    #[test]
+    fn test_vendor_from_id_const() {
+        const VENDOR: Option<&Vendor> = Vendor::from_id_const(0x1d6b);
+        assert_eq!(VENDOR, Vendor::from_id(0x1d6b));
+
+        for vendor in Vendors::iter() {
+            assert_eq!(Vendor::from_id_const(vendor.id()), Some(vendor));
+        }
+        assert!(Vendor::from_id_const(0x0000).is_none());
+        assert!(Vendor::from_id_const(0xfffe).is_none());
+    }
+
+    #[test]
+    fn test_sorted_ids() {
+        assert_eq!(VENDOR_IDS.len(), Vendors::len());
+        assert!(VENDOR_IDS.windows(2).all(|w| w[0] < w[1]));
+        assert!(VENDOR_IDS.iter().all(|&id| Vendor::from_id(id).is_some()));
+        assert!(VENDOR_IDS.binary_search(&0x1d6b).is_ok());
+
+        assert_eq!(CLASS_IDS.len(), Classes::len());
+        assert!(CLASS_IDS.windows(2).all(|w| w[0] < w[1]));
+        assert!(Classes::iter().all(|c| CLASS_IDS.binary_search(&c.id()).is_ok()));
+    }
+
+    #[test]
+    fn test_vendors_search() {
+        let vendors: Vec<_> = Vendors::search("LINUX").collect();
+
+        assert!(vendors.iter().any(|v| v.id() == 0x1d6b));
+        assert!(vendors.windows(2).all(|w| w[0].id() < w[1].id()));
+        assert!(vendors
+            .iter()
+            .all(|v| v.name().to_lowercase().contains("linux")));
+
+        assert_eq!(Vendors::search("").count(), Vendors::iter().count());
+        assert_eq!(Vendors::search("no such vendor name").count(), 0);
+
+        let vendor = Vendor::from_id(0x1d6b).unwrap();
+        let devices: Vec<_> = vendor.search_devices("root hub").collect();
+        assert!(devices.iter().any(|d| d.id() == 0x0003));
+    }
+
+    #[test]
+    #[cfg(feature = "fuzzy")]
+    fn test_vendors_closest() {
+        let vendors = Vendors::closest("Linux Foundaton", 5);
+        assert_eq!(vendors.len(), 5);
+        assert_eq!(vendors[0].id(), 0x1d6b);
+
+        assert_eq!(Vendors::closest("LINUX FOUNDATION", 1)[0].id(), 0x1d6b);
+        assert!(Vendors::closest("Linux Foundation", 0).is_empty());
+    }
 
-impl FromId<u8> for Bias {
-    fn from_id(id: u8) -> Option<&'static Self> {
-        USB_BIASES.get(&id)
+    #[test]
+    fn test_vendors_sorted() {
+        let by_id: Vec<_> = Vendors::iter_sorted_by_id().collect();
+        assert_eq!(by_id.len(), Vendors::len());
+        assert!(by_id.windows(2).all(|w| w[0].id() < w[1].id()));
+        assert!(Vendors::iter().eq(by_id.iter().copied()));
+        assert!(Vendors::iter_sorted_by_id()
+            .rev()
+            .eq(by_id.iter().rev().copied()));
+        assert_eq!(Vendors::iter_sorted_by_name().len(), Vendors::len());
+        assert!(Vendors::id_name_pairs().eq(by_id.iter().map(|v| (v.id(), v.name()))));
+
+        let classes: Vec<_> = Classes::iter().map(|c| c.id()).collect();
+        assert_eq!(classes, CLASS_IDS);
+
+        let by_name: Vec<_> = Vendors::iter_sorted_by_name().collect();
+        assert_eq!(by_name.len(), Vendors::len());
+        assert!(by_name.windows(2).all(|w| {
+            (w[0].name().to_lowercase(), w[0].id()) < (w[1].name().to_lowercase(), w[1].id())
+        }));
     }
-}
 
-impl FromId<u8> for Phy {
-    fn from_id(id: u8) -> Option<&'static Self> {
-        USB_PHYS.get(&id)
+    #[test]
+    fn test_vendors_range() {
+        let count = Vendors::range(0x0000..0x1000).count()
+            + Vendors::range(0x1000..0x8000).count()
+            + Vendors::range(0x8000..=0xffff).count();
+        assert_eq!(count, Vendors::len());
+        assert_eq!(Vendors::range(..).count(), Vendors::len());
+        assert!(Vendors::range(0x8000..=0xffff).eq(Vendors::range(0x8000..)));
+
+        assert!(Vendors::range(0x1d6b..0x1d6c).eq(Vendor::from_id(0x1d6b)));
+        assert!(Vendors::range(0x1d6b..=0x1d6b).eq(Vendor::from_id(0x1d6b)));
+        assert_eq!(Vendors::range(0x1d6b..0x1d6b).count(), 0);
+        let reversed = (Bound::Included(0x2000), Bound::Excluded(0x1000));
+        assert_eq!(Vendors::range(reversed).count(), 0);
+        let exclusive = (Bound::Excluded(0x1d6a), Bound::Excluded(0x1d6c));
+        assert!(Vendors::range(exclusive).eq(Vendor::from_id(0x1d6b)));
     }
-}
 
-impl FromId<u16> for Language {
-    fn from_id(id: u16) -> Option<&'static Self> {
-        USB_LANGS.get(&id)
+    #[test]
+    fn test_vendors_with_id_prefix() {
+        assert_eq!(Vendors::with_id_prefix(0, 0).count(), Vendors::len());
+        assert!(Vendors::with_id_prefix(0x1, 1).eq(Vendors::range(0x1000..0x2000)));
+        assert!(Vendors::with_id_prefix(0x1d, 2).eq(Vendors::range(0x1d00..0x1e00)));
+        assert!(Vendors::with_id_prefix(0x1d6b, 4).eq(Vendor::from_id(0x1d6b)));
+        assert!(Vendors::with_id_prefix(0x1d6b, 5).eq(Vendor::from_id(0x1d6b)));
+        assert_eq!(
+            Vendors::with_id_prefix(0xf, 1).count(),
+            Vendors::iter().filter(|v| v.id() >= 0xf000).count()
+        );
+        assert_eq!(Vendors::with_id_prefix(0x123, 2).count(), 0);
     }
-}
 
-impl FromId<u8> for HidCountryCode {
-    fn from_id(id: u8) -> Option<&'static Self> {
-        USB_HID_CCS.get(&id)
+    #[test]
+    fn test_vendor_devices_with_id_prefix() {
+        let vendor = Vendor::from_id(0x046d).unwrap();
+        assert!(vendor.devices_with_id_prefix(0, 0).eq(vendor.devices()));
+        assert!(vendor
+            .devices_with_id_prefix(0xc5, 2)
+            .eq(vendor.devices().filter(|d| d.id() >> 8 == 0xc5)));
+        assert!(vendor.devices_with_id_prefix(0xc5, 2).len() > 1);
+        assert!(vendor
+            .devices_with_id_prefix(0xc52b, 4)
+            .eq(vendor.device(0xc52b)));
+        assert_eq!(vendor.devices_with_id_prefix(0x123, 2).count(), 0);
     }
-}
 
-impl FromId<u16> for VideoTerminal {
-    fn from_id(id: u16) -> Option<&'static Self> {
-        USB_VIDEO_TERMINALS.get(&id)
+    #[test]
+    fn test_len() {
+        assert_eq!(Vendors::len(), Vendors::iter().count());
+        assert_eq!(VENDOR_COUNT, VENDOR_IDS.len());
+        assert_eq!(Classes::len(), Classes::iter().count());
+        assert_eq!(
+            DEVICE_COUNT,
+            Vendors::iter().map(|v| v.devices().count()).sum::<usize>()
+        );
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[test]
+    fn test_db_version() {
+        assert_eq!(db_version().split('.').count(), 3);
+        assert!(db_date().starts_with(db_version().split('.').next().unwrap()));
+    }
 
     #[test]
-    fn test_from_id() {
+    fn test_devices_iter() {
+        assert_eq!(Devices::iter().count(), DEVICE_COUNT);
+
+        for (vendor, device) in Devices::iter() {
+            assert_eq!(device.vendor(), vendor);
+        }
+    }
+
+    #[test]
+    fn test_devices_with_interfaces() {
         let vendor = Vendor::from_id(0x1d6b).unwrap();
+        let bare = Device::from_vid_pid(0x1d6b, 0x0002).unwrap();
+        let retained: Vec<_> = Devices::retain_with_interfaces(
+            vec![(vendor, bare), (vendor, &FIXTURE_DEVICE)].into_iter(),
+        )
+        .collect();
+        assert_eq!(retained, [(vendor, &FIXTURE_DEVICE)]);
 
-        assert_eq!(vendor.name(), "Linux Foundation");
-        assert_eq!(vendor.id(), 0x1d6b);
+        assert_eq!(
+            Devices::with_interfaces().count(),
+            Interfaces::iter()
+                .map(|(_, d, _)| d.as_vid_pid())
+                .collect::<std::collections::HashSet<_>>()
+                .len()
+        );
     }
 
     #[test]
@@ -668,7 +2776,131 @@ mod tests {
         for device in vendor.devices() {
             assert_eq!(device.vendor(), vendor);
             assert!(!device.name().is_empty());
+            assert_eq!(vendor.device(device.id()), Some(device));
+        }
+
+        assert!(vendor.device(0xfffe).is_none());
+        assert!(vendor.devices_slice().iter().eq(vendor.devices()));
+        assert!(vendor
+            .device_entries()
+            .zip(vendor.devices())
+            .all(|((pid, name), d)| pid == d.id() && name == d.name()));
+
+        let sorted: Vec<_> = vendor.devices_sorted_by_name().collect();
+        assert_eq!(sorted.len(), vendor.devices().count());
+        assert!(sorted
+            .windows(2)
+            .all(|w| (w[0].name().to_lowercase(), w[0].id())
+                < (w[1].name().to_lowercase(), w[1].id())));
+    }
+
+    #[test]
+    fn test_write_tree() {
+        let vendor = Vendor::from_id(0x1d6b).unwrap();
+        let mut tree = String::new();
+        vendor.write_tree(&mut tree).unwrap();
+
+        let mut lines = tree.lines();
+        assert_eq!(lines.next(), Some("1d6b  Linux Foundation"));
+        let devices: Vec<_> = lines.collect();
+        assert_eq!(devices.len(), vendor.devices().count());
+        assert!(devices.contains(&"\t0003  3.0 root hub"));
+        assert!(tree.ends_with('\n'));
+    }
+
+    #[test]
+    #[cfg(feature = "classes")]
+    fn test_name_ref() {
+        fn name(entry: impl AsRef<str>) -> String {
+            entry.as_ref().to_owned()
+        }
+
+        let device = Device::from_vid_pid(0x1d6b, 0x0003).unwrap();
+        assert_eq!(name(device), "3.0 root hub");
+        assert_eq!(name(device.vendor()), "Linux Foundation");
+        assert_eq!(
+            name(Class::from_id(0x03).unwrap()),
+            "Human Interface Device"
+        );
+        assert_eq!(name(Language::from_id(0x0007).unwrap()), "German");
+        assert_eq!(name(Hid::from_id(0x22).unwrap()), "Report");
+
+        let s: &'static str = device.into();
+        assert_eq!(s, device.name());
+        let s: &'static str = SubClass::from_cid_scid(0x03, 0x01).unwrap().into();
+        assert_eq!(s, "Boot Interface Subclass");
+    }
+
+    #[test]
+    fn test_refs() {
+        fn vendor_name(vendor: &impl VendorRef) -> &str {
+            vendor.name()
         }
+        fn vid_pid(device: &impl DeviceRef) -> (u16, u16) {
+            device.as_vid_pid()
+        }
+
+        let device = Device::from_vid_pid(0x1d6b, 0x0003).unwrap();
+        assert_eq!(vendor_name(device.vendor()), "Linux Foundation");
+        assert_eq!(VendorRef::id(device.vendor()), 0x1d6b);
+        assert_eq!(vid_pid(device), (0x1d6b, 0x0003));
+        assert_eq!(DeviceRef::name(device), "3.0 root hub");
+    }
+
+    #[test]
+    #[cfg(feature = "classes")]
+    fn test_ord() {
+        let mut vendors: Vec<_> = Vendors::iter_sorted_by_name().collect();
+        vendors.sort();
+        assert!(vendors.iter().copied().eq(Vendors::iter_sorted_by_id()));
+
+        let a = Device::from_vid_pid(0x1d6b, 0x0003).unwrap();
+        let b = Device::from_vid_pid(0x1d6b, 0x0002).unwrap();
+        let c = Device::from_vid_pid(0x0001, 0x7778).unwrap();
+        let devices: std::collections::BTreeSet<_> = vec![a, b, c, a].into_iter().collect();
+        assert!(devices.into_iter().eq(vec![c, b, a]));
+
+        let class = Class::from_id(0x03).unwrap();
+        assert!(Class::from_id(0x02).unwrap() < class);
+        assert!(class
+            .sub_classes()
+            .collect::<Vec<_>>()
+            .windows(2)
+            .all(|w| w[0] < w[1]));
+
+        let keyboard = Protocol::from_cid_scid_pid(0x03, 0x01, 0x01).unwrap();
+        let mouse = Protocol::from_cid_scid_pid(0x03, 0x01, 0x02).unwrap();
+        assert!(keyboard < mouse);
+    }
+
+    #[test]
+    fn test_hash() {
+        use std::collections::HashSet;
+
+        let devices: HashSet<_> = Devices::iter()
+            .chain(Devices::iter())
+            .map(|(_, d)| d)
+            .collect();
+        assert_eq!(devices.len(), DEVICE_COUNT);
+        assert!(devices.contains(Device::from_vid_pid(0x1d6b, 0x0003).unwrap()));
+
+        let vendors: HashSet<_> = Vendors::iter().chain(Vendors::iter()).collect();
+        assert_eq!(vendors.len(), Vendors::len());
+
+        let usages: HashSet<_> = HidUsagePages::iter().flat_map(|p| p.children()).collect();
+        assert!(usages.len() > HidUsagePage::from_id(0x07).unwrap().children().count());
+    }
+
+    #[test]
+    fn test_exists() {
+        assert!(Vendor::exists(0x1d6b));
+        assert!(!Vendor::exists(0xfffe));
+
+        for (vendor, device) in Devices::iter() {
+            assert!(Device::exists(vendor.id(), device.id()));
+        }
+        assert!(!Device::exists(0x1d6b, 0xfffe));
+        assert!(!Device::exists(0xfffe, 0x0001));
     }
 
     #[test]
@@ -685,6 +2917,10 @@ mod tests {
         let device2 = Device::from_vid_pid(vid, pid).unwrap();
 
         assert_eq!(device, device2);
+        assert_eq!(
+            Device::from_le_bytes(vid.to_le_bytes(), pid.to_le_bytes()),
+            Some(device)
+        );
 
         let last_device = Device::from_vid_pid(0xffee, 0x0100).unwrap();
         assert_eq!(
@@ -694,6 +2930,301 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "device-map")]
+    fn test_device_map() {
+        assert_eq!(USB_DEVICE_IDS.len(), DEVICE_COUNT);
+        for (vendor, device) in Devices::iter() {
+            let (vid, pid) = device.as_vid_pid();
+            assert_eq!(Device::from_vid_pid(vid, pid), vendor.device(pid));
+            assert!(Device::exists(vid, pid));
+        }
+        assert!(Device::from_vid_pid(0x1d6b, 0xfffe).is_none());
+        assert!(!Device::exists(0x1d6b, 0xfffe));
+        assert!(Device::from_vid_pid(0xfffe, 0x0001).is_none());
+    }
+
+    #[test]
+    fn test_sorted_by_id() {
+        for vendor in Vendors::iter() {
+            assert!(vendor.devices.windows(2).all(|w| w[0].id < w[1].id));
+        }
+
+        for class in Classes::iter() {
+            assert!(class.sub_classes.windows(2).all(|w| w[0].id < w[1].id));
+            for subclass in class.sub_classes() {
+                assert!(subclass.protocols.windows(2).all(|w| w[0].id < w[1].id));
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "classes")]
+    fn test_display() {
+        let device = Device::from_vid_pid(0x1d6b, 0x0003).unwrap();
+
+        assert_eq!(device.vendor().to_string(), "1d6b  Linux Foundation");
+        assert_eq!(device.to_string(), "0003  3.0 root hub");
+
+        let protocol = Protocol::from_cid_scid_pid(0x03, 0x01, 0x01).unwrap();
+
+        assert_eq!(
+            Class::from_id(0x03).unwrap().to_string(),
+            "03  Human Interface Device"
+        );
+        assert_eq!(
+            SubClass::from_cid_scid(0x03, 0x01).unwrap().to_string(),
+            "01  Boot Interface Subclass"
+        );
+        assert_eq!(protocol.to_string(), "01  Keyboard");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let device = Device::from_vid_pid(0x1d6b, 0x0003).unwrap();
+        let json = serde_json::to_string(device).unwrap();
+
+        assert!(json.contains("\"name\":\"3.0 root hub\""));
+        assert_eq!(&serde_json::from_str::<Device>(&json).unwrap(), device);
+
+        let vendor = device.vendor();
+        let json = serde_json::to_string(vendor).unwrap();
+        assert_eq!(&serde_json::from_str::<Vendor>(&json).unwrap(), vendor);
+
+        assert!(serde_json::from_str::<Vendor>(r#"{"id":0}"#).is_err());
+
+        // the bundled DB has no interfaces, so the fixture's can't resolve
+        let json = serde_json::to_string(&FIXTURE_INTERFACES[0]).unwrap();
+        assert_eq!(
+            json,
+            r#"{"vendor_id":7531,"device_id":3,"id":0,"name":"Hub"}"#
+        );
+        assert!(serde_json::from_str::<Interface>(&json).is_err());
+    }
+
+    #[cfg(all(feature = "serde", feature = "classes"))]
+    #[test]
+    fn test_serde_classes() {
+        let subclass = SubClass::from_cid_scid(0x03, 0x01).unwrap();
+        let json = serde_json::to_string(subclass).unwrap();
+        assert_eq!(&serde_json::from_str::<SubClass>(&json).unwrap(), subclass);
+        assert_eq!(
+            &serde_json::from_str::<Class>(r#"{"id":3}"#).unwrap(),
+            subclass.class()
+        );
+
+        let protocol = Protocol::from_cid_scid_pid(0x03, 0x01, 0x02).unwrap();
+        let json = serde_json::to_string(protocol).unwrap();
+        assert_eq!(&serde_json::from_str::<Protocol>(&json).unwrap(), protocol);
+        assert!(
+            serde_json::from_str::<Protocol>(r#"{"class_id":3,"sub_class_id":1,"id":127}"#)
+                .is_err()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "classes")]
+    fn test_id_hex() {
+        let device = Device::from_vid_pid(0x1d6b, 0x0003).unwrap();
+
+        assert_eq!(device.vendor().id_hex(), "1d6b");
+        assert_eq!(device.id_hex(), "0003");
+
+        let (class, subclass, protocol) = lookup_class(0x03, 0x01, 0x01);
+        assert_eq!(class.unwrap().id_hex(), "03");
+        assert_eq!(subclass.unwrap().id_hex(), "01");
+        assert_eq!(protocol.unwrap().id_hex(), "01");
+    }
+
+    #[test]
+    #[cfg(feature = "classes")]
+    fn test_class_hierarchy_iter() {
+        for (class, subclass) in SubClasses::iter() {
+            assert_eq!(subclass.class(), class);
+        }
+
+        for (class, subclass, protocol) in Protocols::iter() {
+            assert_eq!(
+                Protocol::from_cid_scid_pid(class.id(), subclass.id(), protocol.id()),
+                Some(protocol)
+            );
+            assert_eq!(protocol.sub_class(), subclass);
+            assert_eq!(subclass.protocol(protocol.id()), Some(protocol));
+            assert_eq!(protocol.class(), class);
+            assert_eq!(
+                protocol.as_cid_scid_pid(),
+                (class.id(), subclass.id(), protocol.id())
+            );
+            assert!(protocol.qualified_name().starts_with(class.name()));
+        }
+
+        let rows: Vec<_> = Classes::flatten().collect();
+        assert_eq!(
+            rows.len(),
+            Classes::len() + SubClasses::iter().count() + Protocols::iter().count()
+        );
+        for (class, subclass, protocol) in rows {
+            match (subclass, protocol) {
+                (None, None) => {}
+                (Some(subclass), None) => assert_eq!(subclass.class(), class),
+                (Some(subclass), Some(protocol)) => assert_eq!(protocol.sub_class(), subclass),
+                (None, Some(_)) => panic!("protocol without a subclass"),
+            }
+        }
+
+        assert!(Protocols::iter().any(|(_, _, p)| p.name() == "Keyboard"));
+    }
+
+    #[test]
+    fn test_interfaces() {
+        let device = &FIXTURE_DEVICE;
+        assert!(device.has_interface_data());
+        assert_eq!(device.interfaces().count(), 2);
+        for interface in device.interfaces() {
+            assert_eq!(interface.device().as_vid_pid(), device.as_vid_pid());
+            assert_eq!(device.interface(interface.id()), Some(interface));
+        }
+        assert_eq!(device.interface(0x02).unwrap().name(), "Debug");
+        assert!(device.interface(0x01).is_none());
+        assert_eq!(
+            device.interfaces_map().collect::<Vec<_>>(),
+            [(0x00, "Hub"), (0x02, "Debug")]
+        );
+
+        let device = Device::from_vid_pid(0x1d6b, 0x0003).unwrap();
+        assert!(!device.has_interface_data());
+        assert!(device.interface(0x00).is_none());
+        assert_eq!(device.interfaces_map().count(), 0);
+
+        assert_eq!(
+            Interfaces::iter().count(),
+            Devices::iter()
+                .map(|(_, d)| d.interfaces().count())
+                .sum::<usize>()
+        );
+    }
+
+    #[test]
+    #[cfg(not(feature = "interfaces"))]
+    fn test_without_interfaces() {
+        assert_eq!(Interfaces::iter().count(), 0);
+    }
+
+    #[test]
+    #[cfg(not(feature = "classes"))]
+    fn test_without_classes() {
+        assert_eq!(Classes::iter().count(), 0);
+        assert!(CLASS_IDS.is_empty());
+        assert_eq!(lookup_class(0x03, 0x01, 0x02), (None, None, None));
+    }
+
+    #[test]
+    fn test_from_vid_pids() {
+        let mut pairs: Vec<_> = Devices::iter().map(|(v, d)| (v.id(), d.id())).collect();
+        pairs.reverse();
+        pairs.extend_from_slice(&[(0xfffe, 0x0001), (0x1d6b, 0xfffe)]);
+        let devices = Device::from_vid_pids(&pairs);
+
+        assert_eq!(devices.len(), pairs.len());
+        for (&(vid, pid), device) in pairs.iter().zip(devices) {
+            assert_eq!(device, Device::from_vid_pid(vid, pid));
+        }
+        assert!(Device::from_vid_pids(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_try_from_vid_pid() {
+        assert_eq!(
+            Device::try_from_vid_pid(0x1d6b, 0x0003).ok(),
+            Device::from_vid_pid(0x1d6b, 0x0003)
+        );
+
+        let err = Device::try_from_vid_pid(0x1d6b, 0xfffe).unwrap_err();
+        assert_eq!(err.to_string(), "unknown device 1d6b:fffe");
+
+        let err = Device::try_from_vid_pid(0xfffe, 0x0001).unwrap_err();
+        assert_eq!(err, LookupError::UnknownVendor(0xfffe));
+        assert_eq!(err.to_string(), "unknown vendor fffe");
+
+        assert_eq!(
+            <&Device>::try_from((0x1d6b, 0x0003)),
+            Device::try_from_vid_pid(0x1d6b, 0x0003)
+        );
+        assert_eq!(
+            <&Device>::try_from((0x1d6b, 0xfffe)),
+            Err(LookupError::UnknownDevice {
+                vid: 0x1d6b,
+                pid: 0xfffe
+            })
+        );
+        assert_eq!(
+            <&Vendor>::try_from(0x1d6b),
+            Ok(Vendor::from_id(0x1d6b).unwrap())
+        );
+        assert_eq!(
+            <&Vendor>::try_from(0xfffe),
+            Err(LookupError::UnknownVendor(0xfffe))
+        );
+    }
+
+    #[test]
+    fn test_hash_in_name() {
+        let device = Device::from_vid_pid(0x0461, 0x0302).unwrap();
+        assert_eq!(device.name(), "G2-300 #2 Scanner");
+    }
+
+    #[test]
+    fn test_name_or_unknown() {
+        assert_eq!(
+            Device::name_or_unknown(0x1d6b, 0x0002),
+            "Linux Foundation / 2.0 root hub"
+        );
+        assert_eq!(
+            Device::name_or_unknown(0x1d6b, 0xfffe),
+            "Linux Foundation / Unknown Product (0xfffe)"
+        );
+        assert_eq!(
+            Device::name_or_unknown(0xfffe, 0x00ab),
+            "Unknown Vendor (0xfffe) / Unknown Product (0x00ab)"
+        );
+    }
+
+    #[test]
+    fn test_vid_pid_from_str() {
+        let expected = VidPid {
+            vid: 0x1d6b,
+            pid: 0x0003,
+        };
+
+        for s in ["1d6b:0003", "0x1d6b:0x0003", "1D6B:3", " 0X1d6B:0003\n"] {
+            assert_eq!(s.parse::<VidPid>(), Ok(expected));
+        }
+
+        for s in [
+            "1d6b",
+            "1d6b:",
+            ":0003",
+            "1d6b:00003",
+            "1d6b:+003",
+            "xyz:0003",
+        ] {
+            assert_eq!(s.parse::<VidPid>(), Err(VidPidError::Malformed));
+        }
+
+        let device: &Device = "1d6b:0003".parse().unwrap();
+        assert_eq!(device.as_vid_pid(), (0x1d6b, 0x0003));
+
+        assert_eq!(
+            "1d6b:fffe".parse::<&Device>(),
+            Err(VidPidError::UnknownDevice(VidPid {
+                vid: 0x1d6b,
+                pid: 0xfffe
+            }))
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "classes")]
     fn test_class_from_id() {
         let class = Class::from_id(0x03).unwrap();
 
@@ -702,14 +3233,22 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "classes")]
     fn test_subclass_from_cid_scid() {
         let subclass = SubClass::from_cid_scid(0x03, 0x01).unwrap();
 
         assert_eq!(subclass.name(), "Boot Interface Subclass");
         assert_eq!(subclass.id(), 0x01);
+
+        let class = Class::from_id(0x03).unwrap();
+        assert_eq!(class.sub_class(0x01), Some(subclass));
+        assert!(class.sub_class(0xfe).is_none());
+        assert!(class.sub_classes_slice().iter().eq(class.sub_classes()));
+        assert!(subclass.protocols_slice().iter().eq(subclass.protocols()));
     }
 
     #[test]
+    #[cfg(feature = "classes")]
     fn test_protocol_from_cid_scid_pid() {
         let protocol = Protocol::from_cid_scid_pid(0x03, 0x01, 0x01).unwrap();
 
@@ -728,12 +3267,114 @@ mod tests {
         assert_eq!(protocol.id(), 0xff);
     }
 
+    #[test]
+    #[cfg(feature = "classes")]
+    fn test_class_predicates() {
+        let class = |id| Class::from_id(id).unwrap();
+        assert_eq!(class(0x03).name(), "Human Interface Device");
+        assert_eq!(class(0x08).name(), "Mass Storage");
+        assert_eq!(class(0x09).name(), "Hub");
+
+        assert_eq!(class(0x00).name(), "(Defined at Interface level)");
+        assert!(class(0x00).is_per_interface());
+        assert_eq!(Classes::iter().filter(|c| c.is_per_interface()).count(), 1);
+        assert_eq!(Classes::iter().filter(|c| c.is_hid()).count(), 1);
+        assert_eq!(Classes::iter().filter(|c| c.is_mass_storage()).count(), 1);
+        assert_eq!(Classes::iter().filter(|c| c.is_hub()).count(), 1);
+        assert_eq!(SubClasses::iter().filter(|(_, s)| s.is_iad()).count(), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "classes")]
+    fn test_vendor_specific() {
+        let class = Class::from_id(0xff).unwrap();
+        assert!(class.is_vendor_specific());
+        assert_eq!(
+            Classes::iter().filter(|c| c.is_vendor_specific()).count(),
+            1
+        );
+
+        for id in 0..=0xff {
+            assert!(SubClass::name_or_vendor_specific(0xff, id).is_some());
+        }
+        assert_eq!(
+            SubClass::name_or_vendor_specific(0xff, 0xff),
+            Some(SubClass::from_cid_scid(0xff, 0xff).unwrap().name())
+        );
+        assert_eq!(SubClass::name_or_vendor_specific(0x42, 0x01), None);
+    }
+
+    #[test]
+    #[cfg(feature = "classes")]
+    fn test_lookup_class() {
+        let (class, subclass, protocol) = lookup_class(0x07, 0x01, 0x03);
+
+        assert_eq!(class, Class::from_id(0x07));
+        assert_eq!(subclass, SubClass::from_cid_scid(0x07, 0x01));
+        assert_eq!(protocol, Protocol::from_cid_scid_pid(0x07, 0x01, 0x03));
+        assert!(protocol.is_some());
+
+        let (class, subclass, protocol) = lookup_class(0x03, 0x01, 0x7f);
+        assert!(class.is_some() && subclass.is_some());
+        assert!(protocol.is_none());
+
+        assert_eq!(lookup_class(0xfe, 0xfe, 0xfe).0, Class::from_id(0xfe));
+        assert_eq!(lookup_class(0x42, 0x01, 0x01), (None, None, None));
+    }
+
+    #[test]
+    #[cfg(feature = "classes")]
+    fn test_class_path() {
+        let path = ClassPath::from_triple(0x03, 0x01, 0x7f).unwrap();
+
+        assert_eq!(path.class, Class::from_id(0x03));
+        assert_eq!(path.sub_class, SubClass::from_cid_scid(0x03, 0x01));
+        assert!(path.protocol.is_none());
+        assert_eq!(
+            (path.class_id, path.sub_class_id, path.protocol_id),
+            (0x03, 0x01, 0x7f)
+        );
+        assert_eq!(
+            path.to_string(),
+            "Human Interface Device / Boot Interface Subclass / Unknown (0x7f)"
+        );
+
+        let path = ClassPath::from_triple(0x03, 0x7f, 0x01).unwrap();
+        assert!(path.sub_class.is_none() && path.protocol.is_none());
+        assert_eq!(
+            path.to_string(),
+            "Human Interface Device / Unknown (0x7f) / Unknown (0x01)"
+        );
+
+        assert!(ClassPath::from_triple(0x42, 0x01, 0x01).is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "classes")]
+    fn test_class_path_from_interface_descriptor() {
+        let path = ClassPath::from_interface_descriptor(0x03, 0x01, 0x01);
+        assert_eq!(
+            path.to_string(),
+            "Human Interface Device / Boot Interface Subclass / Keyboard"
+        );
+        assert_eq!(Some(path), ClassPath::from_triple(0x03, 0x01, 0x01));
+
+        let path = ClassPath::from_interface_descriptor(0x42, 0x01, 0x01);
+        assert!(path.class.is_none() && path.sub_class.is_none() && path.protocol.is_none());
+        assert_eq!(
+            path.to_string(),
+            "Unknown (0x42) / Unknown (0x01) / Unknown (0x01)"
+        );
+    }
+
     #[test]
     fn test_at_from_id() {
         let at = AudioTerminal::from_id(0x0713).unwrap();
 
         assert_eq!(at.name(), "Synthesizer");
         assert_eq!(at.id(), 0x0713);
+
+        assert!(AudioTerminals::iter().any(|at| at.id() == 0x0201));
     }
 
     #[test]
@@ -742,6 +3383,8 @@ mod tests {
 
         assert_eq!(hid.name(), "Physical");
         assert_eq!(hid.id(), 0x23);
+
+        assert!(Hids::iter().any(|hid| hid.id() == 0x22));
     }
 
     #[test]
@@ -750,6 +3393,8 @@ mod tests {
 
         assert_eq!(hid_type.name(), "End Collection");
         assert_eq!(hid_type.id(), 0xc0);
+
+        assert!(HidItemTypes::iter().any(|hid_type| hid_type.id() == 0xb4));
     }
 
     #[test]
@@ -758,6 +3403,8 @@ mod tests {
 
         assert_eq!(bias.name(), "Either Hand");
         assert_eq!(bias.id(), 0x04);
+
+        assert!(Biases::iter().any(|bias| bias.id() == 0x02));
     }
 
     #[test]
@@ -766,6 +3413,8 @@ mod tests {
 
         assert_eq!(phy.name(), "Cheek");
         assert_eq!(phy.id(), 0x27);
+
+        assert!(Phys::iter().any(|phy| phy.id() == 0x25));
     }
 
     #[test]
@@ -779,6 +3428,27 @@ mod tests {
 
         assert_eq!(hid_usage.name(), "Digitizer");
         assert_eq!(hid_usage.id(), 0x01);
+
+        // not valid UTF-8 in usb.ids, but should not be dropped
+        let hid_usage = HidUsage::from_pageid_uid(0x07, 0x034).unwrap();
+        assert!(hid_usage
+            .name()
+            .ends_with("and \" (Accent Acute and Double Quotes)"));
+
+        let hid_usage = HidUsage::from_page_usage(0x000d, 0x01).unwrap();
+        assert_eq!(hid_usage.name(), "Digitizer");
+
+        assert!(HidUsage::from_page_usage(0x010d, 0x01).is_none());
+
+        // the same usage ID on different pages
+        let pointer = HidUsage::from_pageid_uid(0x01, 0x001).unwrap();
+        assert_eq!(pointer.name(), "Pointer");
+        let flight = HidUsage::from_pageid_uid(0x02, 0x001).unwrap();
+        assert_eq!(flight.name(), "Flight Simulation Device");
+        assert_eq!(
+            HidUsage::from_pageid_uid(0x01, 0x002).unwrap().name(),
+            "Mouse"
+        );
     }
 
     #[test]
@@ -792,6 +3462,13 @@ mod tests {
 
         assert_eq!(dialect.name(), "Swiss");
         assert_eq!(dialect.id(), 0x02);
+
+        // 0x0807 is Swiss German: primary language 0x07, sub-language 0x02
+        assert_eq!(Language::from_langid(0x0807), Some(language));
+        assert_eq!(language.primary_name(), "German");
+        assert_eq!(Dialect::from_langid(0x0807), Some(dialect));
+        assert_eq!(Dialect::from_lang_dialect(0x0007, 0x02), Some(dialect));
+        assert!(Dialect::from_langid(0x0007).is_none());
     }
 
     #[test]
@@ -803,6 +3480,8 @@ mod tests {
 
         let hid_country_code = HidCountryCode::from_id(0x00).unwrap();
         assert_eq!(hid_country_code.name(), "Not supported");
+
+        assert!(HidCountryCodes::iter().any(|hcc| hcc.id() == 0x21));
     }
 
     #[test]
@@ -814,5 +3493,7 @@ mod tests {
 
         let video_terminal = VideoTerminal::from_id(0x0403).unwrap();
         assert_eq!(video_terminal.name(), "Component Video");
+
+        assert!(VideoTerminals::iter().any(|vt| vt.id() == 0x0101));
     }
 }
@@ -0,0 +1,118 @@
+//! Free functions for every lookup in the USB database, from a single import.
+//!
+//! Each function delegates to the constructor on the corresponding type, e.g.
+//! [`lookup::device`](device) to [`Device::from_vid_pid`], so the two are
+//! interchangeable.
+//!
+//! ```
+//! use usb_ids::lookup;
+//!
+//! assert_eq!(lookup::vendor(0x1d6b).unwrap().name(), "Linux Foundation");
+//! assert_eq!(lookup::device(0x1d6b, 0x0003).unwrap().name(), "3.0 root hub");
+//! # #[cfg(feature = "classes")]
+//! assert_eq!(lookup::protocol(0x03, 0x01, 0x02).unwrap().name(), "Mouse");
+//! ```
+
+use crate::{
+    AudioTerminal, Bias, Class, Device, Dialect, FromId, Hid, HidCountryCode, HidItemType,
+    HidUsage, HidUsagePage, Interface, Language, Phy, Protocol, SubClass, Vendor, VideoTerminal,
+};
+
+/// Returns the entity of type `T` with the given ID, as with [`FromId::from_id`].
+///
+/// ```
+/// # #[cfg(feature = "classes")] {
+/// use usb_ids::{lookup, Class};
+/// let class: &Class = lookup::by_id(0x03).unwrap();
+/// assert_eq!(class.name(), "Human Interface Device");
+/// # }
+/// ```
+pub fn by_id<T: FromId<I>, I>(id: I) -> Option<&'static T> {
+    T::from_id(id)
+}
+
+/// Returns the [`Vendor`] with the given ID.
+pub fn vendor(id: u16) -> Option<&'static Vendor> {
+    Vendor::from_id(id)
+}
+
+/// Returns the [`Device`] with the given vendor and product IDs.
+pub fn device(vid: u16, pid: u16) -> Option<&'static Device> {
+    Device::from_vid_pid(vid, pid)
+}
+
+/// Returns the [`Interface`] with the given ID on the device with the given vendor
+/// and product IDs.
+pub fn interface(vid: u16, pid: u16, id: u8) -> Option<&'static Interface> {
+    Device::from_vid_pid(vid, pid)?.interface(id)
+}
+
+/// Returns the [`Class`] with the given ID.
+pub fn class(id: u8) -> Option<&'static Class> {
+    Class::from_id(id)
+}
+
+/// Returns the [`SubClass`] with the given class and subclass IDs.
+pub fn sub_class(class_id: u8, id: u8) -> Option<&'static SubClass> {
+    SubClass::from_cid_scid(class_id, id)
+}
+
+/// Returns the [`Protocol`] with the given class, subclass and protocol IDs.
+pub fn protocol(class_id: u8, subclass_id: u8, id: u8) -> Option<&'static Protocol> {
+    Protocol::from_cid_scid_pid(class_id, subclass_id, id)
+}
+
+/// Returns the [`AudioTerminal`] with the given ID.
+pub fn audio_terminal(id: u16) -> Option<&'static AudioTerminal> {
+    AudioTerminal::from_id(id)
+}
+
+/// Returns the [`VideoTerminal`] with the given ID.
+pub fn video_terminal(id: u16) -> Option<&'static VideoTerminal> {
+    VideoTerminal::from_id(id)
+}
+
+/// Returns the [`Hid`] descriptor type with the given ID.
+pub fn hid(id: u8) -> Option<&'static Hid> {
+    Hid::from_id(id)
+}
+
+/// Returns the [`HidItemType`] with the given ID.
+pub fn hid_item_type(id: u8) -> Option<&'static HidItemType> {
+    HidItemType::from_id(id)
+}
+
+/// Returns the [`HidUsagePage`] with the given ID.
+pub fn hid_usage_page(id: u8) -> Option<&'static HidUsagePage> {
+    HidUsagePage::from_id(id)
+}
+
+/// Returns the [`HidUsage`] with the given usage page and usage ID.
+pub fn hid_usage(page_id: u8, id: u16) -> Option<&'static HidUsage> {
+    HidUsage::from_pageid_uid(page_id, id)
+}
+
+/// Returns the [`HidCountryCode`] with the given ID.
+pub fn hid_country_code(id: u8) -> Option<&'static HidCountryCode> {
+    HidCountryCode::from_id(id)
+}
+
+/// Returns the [`Bias`] with the given ID.
+pub fn bias(id: u8) -> Option<&'static Bias> {
+    Bias::from_id(id)
+}
+
+/// Returns the [`Phy`] with the given ID.
+pub fn phy(id: u8) -> Option<&'static Phy> {
+    Phy::from_id(id)
+}
+
+/// Returns the [`Language`] with the given ID.
+pub fn language(id: u16) -> Option<&'static Language> {
+    Language::from_id(id)
+}
+
+/// Returns the [`Dialect`] with the given language and dialect IDs.
+pub fn dialect(language_id: u16, id: u8) -> Option<&'static Dialect> {
+    Dialect::from_lid_did(language_id, id)
+}
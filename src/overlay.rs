@@ -0,0 +1,94 @@
+//! Runtime additions on top of the embedded database.
+//!
+//! Some vendor and device IDs (internal or prototype hardware, for example) will
+//! never be in upstream `usb.ids`. An [`Overlay`] names them without forking the
+//! crate or replacing the whole database: it is consulted first, and lookups fall
+//! back to the embedded database.
+//!
+//! ```
+//! use usb_ids::Overlay;
+//!
+//! let overlay = Overlay::new()
+//!     .with_vendor(0xfffe, "Acme Prototypes")
+//!     .with_device(0xfffe, 0x0001, "My Proto")
+//!     .with_device(0x1d6b, 0x0003, "Root hub (USB 3)");
+//!
+//! assert_eq!(overlay.device(0xfffe, 0x0001), Some("My Proto"));
+//! assert_eq!(overlay.device(0x1d6b, 0x0003), Some("Root hub (USB 3)"));
+//! // falls back to the embedded database
+//! assert_eq!(overlay.device(0x1d6b, 0x0002), Some("2.0 root hub"));
+//! assert_eq!(overlay.vendor(0x1d6b), Some("Linux Foundation"));
+//! ```
+
+use std::collections::HashMap;
+
+use crate::{Device, FromId, Vendor};
+
+/// Vendor and device names layered over the embedded database.
+///
+/// Entries in the overlay take precedence over (and may replace) those in the
+/// embedded database.
+#[derive(Clone, Debug, Default)]
+pub struct Overlay {
+    vendors: HashMap<u16, String>,
+    devices: HashMap<(u16, u16), String>,
+}
+
+impl Overlay {
+    /// Returns an empty overlay, which resolves exactly like the embedded database.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds (or replaces) the name of the vendor with the given ID.
+    pub fn with_vendor(mut self, vid: u16, name: impl Into<String>) -> Self {
+        self.vendors.insert(vid, name.into());
+        self
+    }
+
+    /// Adds (or replaces) the name of the device with the given vendor and product IDs.
+    pub fn with_device(mut self, vid: u16, pid: u16, name: impl Into<String>) -> Self {
+        self.devices.insert((vid, pid), name.into());
+        self
+    }
+
+    /// Returns the name of the vendor with the given ID, from the overlay if present
+    /// and otherwise from the embedded database.
+    pub fn vendor(&self, vid: u16) -> Option<&str> {
+        match self.vendors.get(&vid) {
+            Some(name) => Some(name),
+            None => Vendor::from_id(vid).map(|v| v.name()),
+        }
+    }
+
+    /// Returns the name of the device with the given vendor and product IDs, from
+    /// the overlay if present and otherwise from the embedded database.
+    pub fn device(&self, vid: u16, pid: u16) -> Option<&str> {
+        match self.devices.get(&(vid, pid)) {
+            Some(name) => Some(name),
+            None => Device::from_vid_pid(vid, pid).map(|d| d.name()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_overlay() {
+        let overlay = Overlay::new();
+        assert_eq!(overlay.vendor(0x1d6b), Some("Linux Foundation"));
+        assert_eq!(overlay.device(0x1d6b, 0x0003), Some("3.0 root hub"));
+        assert!(overlay.device(0xfffe, 0x0001).is_none());
+
+        let overlay = overlay
+            .with_device(0xfffe, 0x0001, "My Proto")
+            .with_device(0xfffe, 0x0001, String::from("My Proto v2"))
+            .with_vendor(0x1d6b, "Linux");
+        assert_eq!(overlay.device(0xfffe, 0x0001), Some("My Proto v2"));
+        assert!(overlay.vendor(0xfffe).is_none());
+        assert_eq!(overlay.vendor(0x1d6b), Some("Linux"));
+        assert_eq!(overlay.device(0x1d6b, 0x0003), Some("3.0 root hub"));
+    }
+}
@@ -0,0 +1,246 @@
+//! Line parsers for the `usb.ids` format.
+//!
+//! Each parser matches the prefix of one kind of line, up to and including the two
+//! spaces before the name, and returns the rest of the line (the name) with the ID.
+//! Only the prefix is matched against the indentation, so the name is kept verbatim,
+//! tabs and all, apart from trailing whitespace.
+//!
+//! This module is shared by the build script, which includes it by path, and the
+//! runtime `Database` (with the `parse` feature), so both read the same format.
+
+use core::num::ParseIntError;
+
+use nom::bytes::complete::{tag, take};
+use nom::character::complete::{hex_digit1, space1, tab};
+use nom::combinator::{all_consuming, map_parser, map_res};
+use nom::sequence::{delimited, terminated};
+use nom::IResult;
+
+fn id<T, F>(size: usize, from_str_radix: F) -> impl Fn(&str) -> IResult<&str, T>
+where
+    F: Fn(&str, u32) -> Result<T, ParseIntError>,
+{
+    move |input| {
+        map_res(map_parser(take(size), all_consuming(hex_digit1)), |input| {
+            from_str_radix(input, 16)
+        })(input)
+    }
+}
+
+/// Strips the line ending (`\n` or `\r\n`) from a line read from `usb.ids`, so
+/// that a checkout with CRLF line endings parses the same as one without.
+pub fn line(input: &str) -> &str {
+    let input = input.strip_suffix('\n').unwrap_or(input);
+    input.strip_suffix('\r').unwrap_or(input)
+}
+
+/// Trims trailing whitespace, which some entries in `usb.ids` have, from the name
+/// left over by an entry parser.
+fn name<T>(result: IResult<&str, T>) -> IResult<&str, T> {
+    result.map(|(name, id)| (name.trim_end(), id))
+}
+
+pub fn version(input: &str) -> IResult<&str, &str> {
+    terminated(tag("# Version:"), space1)(input)
+}
+
+pub fn date(input: &str) -> IResult<&str, &str> {
+    terminated(tag("# Date:"), space1)(input)
+}
+
+pub fn vendor(input: &str) -> IResult<&str, u16> {
+    let id = id(4, u16::from_str_radix);
+    name(terminated(id, tag("  "))(input))
+}
+
+pub fn device(input: &str) -> IResult<&str, u16> {
+    let id = id(4, u16::from_str_radix);
+    name(delimited(tab, id, tag("  "))(input))
+}
+
+pub fn interface(input: &str) -> IResult<&str, u8> {
+    let id = id(2, u8::from_str_radix);
+    name(delimited(tag("\t\t"), id, tag("  "))(input))
+}
+
+pub fn class(input: &str) -> IResult<&str, u8> {
+    let id = id(2, u8::from_str_radix);
+    name(delimited(tag("C "), id, tag("  "))(input))
+}
+
+pub fn sub_class(input: &str) -> IResult<&str, u8> {
+    let id = id(2, u8::from_str_radix);
+    name(delimited(tab, id, tag("  "))(input))
+}
+
+pub fn protocol(input: &str) -> IResult<&str, u8> {
+    let id = id(2, u8::from_str_radix);
+    name(delimited(tag("\t\t"), id, tag("  "))(input))
+}
+
+pub fn audio_terminal_type(input: &str) -> IResult<&str, u16> {
+    let id = id(4, u16::from_str_radix);
+    name(delimited(tag("AT "), id, tag("  "))(input))
+}
+
+pub fn hid_type(input: &str) -> IResult<&str, u8> {
+    let id = id(2, u8::from_str_radix);
+    name(delimited(tag("HID "), id, tag("  "))(input))
+}
+
+pub fn hid_item_type(input: &str) -> IResult<&str, u8> {
+    let id = id(2, u8::from_str_radix);
+    name(delimited(tag("R "), id, tag("  "))(input))
+}
+
+pub fn bias_type(input: &str) -> IResult<&str, u8> {
+    let id = id(1, u8::from_str_radix);
+    name(delimited(tag("BIAS "), id, tag("  "))(input))
+}
+
+pub fn phy_type(input: &str) -> IResult<&str, u8> {
+    let id = id(2, u8::from_str_radix);
+    name(delimited(tag("PHY "), id, tag("  "))(input))
+}
+
+pub fn hut_type(input: &str) -> IResult<&str, u8> {
+    let id = id(2, u8::from_str_radix);
+    name(delimited(tag("HUT "), id, tag("  "))(input))
+}
+
+pub fn hid_usage_name(input: &str) -> IResult<&str, u16> {
+    let id = id(3, u16::from_str_radix);
+    name(delimited(tab, id, tag("  "))(input))
+}
+
+pub fn language(input: &str) -> IResult<&str, u16> {
+    let id = id(4, u16::from_str_radix);
+    name(delimited(tag("L "), id, tag("  "))(input))
+}
+
+pub fn dialect(input: &str) -> IResult<&str, u8> {
+    let id = id(2, u8::from_str_radix);
+    name(delimited(tab, id, tag("  "))(input))
+}
+
+pub fn country_code(input: &str) -> IResult<&str, u8> {
+    let id = id(2, u8::from_str_radix);
+    name(delimited(tag("HCC "), id, tag("  "))(input))
+}
+
+pub fn terminal_type(input: &str) -> IResult<&str, u16> {
+    let id = id(4, u16::from_str_radix);
+    name(delimited(tag("VT "), id, tag("  "))(input))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vendor_device_interface() {
+        assert_eq!(
+            vendor("1d6b  Linux Foundation"),
+            Ok(("Linux Foundation", 0x1d6b))
+        );
+        assert_eq!(device("\t0003  3.0 root hub"), Ok(("3.0 root hub", 0x0003)));
+        assert_eq!(
+            interface("\t\t00  Hub interface"),
+            Ok(("Hub interface", 0x00))
+        );
+
+        // each level only matches its own indentation
+        assert!(vendor("\t0003  3.0 root hub").is_err());
+        assert!(device("\t\t00  Hub interface").is_err());
+        assert!(device("1d6b  Linux Foundation").is_err());
+        assert!(interface("\t0003  3.0 root hub").is_err());
+
+        // interface numbers are two hex digits
+        assert_eq!(interface("\t\tff  Last"), Ok(("Last", 0xff)));
+        assert!(interface("\t\t000  Too wide").is_err());
+    }
+
+    #[test]
+    fn test_trailing_whitespace() {
+        assert_eq!(
+            device("\t002d  WL-302 Wireless Network 300N USB dongle "),
+            Ok(("WL-302 Wireless Network 300N USB dongle", 0x002d))
+        );
+        assert_eq!(
+            vendor("1d6b  Linux Foundation \t"),
+            Ok(("Linux Foundation", 0x1d6b))
+        );
+        assert_eq!(
+            class("C 03  Human Interface Device  "),
+            Ok(("Human Interface Device", 0x03))
+        );
+    }
+
+    #[test]
+    fn test_hut_nesting() {
+        // the nesting itself is checked against the generated tables in lib.rs
+        assert_eq!(
+            hut_type("HUT 01  Generic Desktop Controls"),
+            Ok(("Generic Desktop Controls", 0x01))
+        );
+        assert_eq!(hid_usage_name("\t001  Pointer"), Ok(("Pointer", 0x001)));
+
+        // neither level parses as the other
+        assert!(hut_type("\t001  Pointer").is_err());
+        assert!(hid_usage_name("HUT 01  Generic Desktop Controls").is_err());
+        assert!(hid_usage_name("\t01  Too narrow").is_err());
+    }
+
+    #[test]
+    fn test_hash_in_name() {
+        // only whole-line comments are comments; a `#` in a name is kept verbatim
+        assert_eq!(
+            device("\t0302  G2-300 #2 Scanner"),
+            Ok(("G2-300 #2 Scanner", 0x0302))
+        );
+        assert_eq!(vendor("fffe  USB# Adapter"), Ok(("USB# Adapter", 0xfffe)));
+        assert_eq!(
+            vendor("fffe  Adapter # not a comment"),
+            Ok(("Adapter # not a comment", 0xfffe))
+        );
+        assert!(vendor("# fffe  Comment").is_err());
+    }
+
+    #[test]
+    fn test_tab_in_name() {
+        // tabs only count as indentation before the ID
+        assert_eq!(
+            device("\t0003  3.0\troot hub"),
+            Ok(("3.0\troot hub", 0x0003))
+        );
+        assert_eq!(
+            interface("\t\t00  Hub\t\tinterface"),
+            Ok(("Hub\t\tinterface", 0x00))
+        );
+        assert_eq!(protocol("\t\t01  Key\tboard"), Ok(("Key\tboard", 0x01)));
+        assert_eq!(
+            vendor("1d6b  \tLinux Foundation"),
+            Ok(("\tLinux Foundation", 0x1d6b))
+        );
+
+        // a tab is not the separator
+        assert!(device("\t0003\t3.0 root hub").is_err());
+    }
+
+    #[test]
+    fn test_crlf() {
+        assert_eq!(line("1d6b  Linux Foundation\r\n"), "1d6b  Linux Foundation");
+        assert_eq!(line("1d6b  Linux Foundation\n"), "1d6b  Linux Foundation");
+        assert_eq!(line("1d6b  Linux Foundation"), "1d6b  Linux Foundation");
+        assert_eq!(line("\r\n"), "");
+
+        assert_eq!(
+            vendor(line("1d6b  Linux Foundation\r\n")),
+            Ok(("Linux Foundation", 0x1d6b))
+        );
+        assert_eq!(
+            device(line("\t0003  3.0 root hub\r\n")),
+            Ok(("3.0 root hub", 0x0003))
+        );
+    }
+}
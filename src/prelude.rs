@@ -0,0 +1,17 @@
+//! The commonly used types and traits, for a single glob import.
+//!
+//! ```
+//! use usb_ids::prelude::*;
+//!
+//! let vendor = Vendor::from_id(0x1d6b).unwrap();
+//! assert_eq!(vendor.name(), "Linux Foundation");
+//!
+//! for (vendor, device) in Devices::iter().take(3) {
+//!     println!("{} {}", vendor.name(), device.name());
+//! }
+//! ```
+
+pub use crate::{
+    Class, Classes, Device, Devices, FromId, Interface, Interfaces, Protocol, Protocols, SubClass,
+    SubClasses, Vendor, Vendors,
+};
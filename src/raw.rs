@@ -0,0 +1,41 @@
+//! The generated lookup tables behind the public API.
+//!
+//! **NOTE**: These are internals, exposed by the `unstable` feature for building
+//! custom indices. Their types and layout may change in any release, including
+//! patch releases.
+//!
+//! ```
+//! use usb_ids::raw;
+//!
+//! // vendor IDs index into the ID-sorted vendor table
+//! let index = raw::USB_IDS[&0x1d6b];
+//! assert_eq!(raw::USB_VENDORS[index as usize].name(), "Linux Foundation");
+//!
+//! for (id, class) in raw::USB_CLASSES.entries() {
+//!     assert_eq!(*id, class.id());
+//! }
+//! ```
+
+use crate::{Class, Vendor};
+
+/// Vendor ID to the index of the vendor in [`USB_VENDORS`].
+pub static USB_IDS: &phf::Map<u16, u16> = &crate::USB_IDS;
+
+/// Every vendor, in ascending ID order.
+pub static USB_VENDORS: &[Vendor] = crate::USB_VENDORS;
+
+/// Lowercased vendor name to the lowest ID of the vendors with that name.
+pub static USB_VENDOR_NAMES: &phf::Map<&str, u16> = &crate::USB_VENDOR_NAMES;
+
+/// Indices into [`USB_VENDORS`], ordered by vendor name (ignoring case) and then ID.
+pub static USB_VENDORS_BY_NAME: &[u16] = crate::USB_VENDORS_BY_NAME;
+
+/// Packed vendor and product ID (`vid << 16 | pid`) to the vendor's index in
+/// [`USB_VENDORS`] and the device's index in its vendor's devices.
+///
+/// Only present with the `device-map` feature.
+#[cfg(feature = "device-map")]
+pub static USB_DEVICE_IDS: &phf::Map<u32, (u16, u16)> = &crate::USB_DEVICE_IDS;
+
+/// Class ID to class.
+pub static USB_CLASSES: &phf::Map<u8, Class> = &crate::USB_CLASSES;
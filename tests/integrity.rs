@@ -0,0 +1,42 @@
+//! Structural checks over the generated database: every entry must resolve back
+//! to the parent it is listed under.
+
+use usb_ids::{Classes, Device, Devices, FromId, Vendor};
+
+#[test]
+fn test_devices_resolve_vendor() {
+    for (vendor, device) in Devices::iter() {
+        let (vid, pid) = device.as_vid_pid();
+        assert_eq!(vid, vendor.id(), "{:04x}:{:04x}", vid, pid);
+        assert_eq!(
+            Vendor::from_id(vid),
+            Some(vendor),
+            "{:04x}:{:04x}",
+            vid,
+            pid
+        );
+        assert_eq!(device.vendor(), vendor);
+        assert_eq!(Device::from_vid_pid(vid, pid), Some(device));
+    }
+}
+
+#[test]
+fn test_class_hierarchy_resolves() {
+    for class in Classes::iter() {
+        for sub_class in class.sub_classes() {
+            assert_eq!(sub_class.class(), class);
+            for protocol in sub_class.protocols() {
+                assert_eq!(protocol.sub_class(), sub_class);
+                assert_eq!(protocol.class(), class);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_names_trimmed() {
+    for (vendor, device) in Devices::iter() {
+        assert_eq!(vendor.name(), vendor.name().trim_end());
+        assert_eq!(device.name(), device.name().trim_end(), "{:?}", device);
+    }
+}